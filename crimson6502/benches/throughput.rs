@@ -0,0 +1,77 @@
+//! emulation throughput. the cycles benchmarks report emulated cycles per second, so 1 Melem/s
+//! is the speed of a 1 MHz 6502.
+//! run with `cargo bench -p crimson6502`
+
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use crimson6502::{CPU, CPUState, CPUVariant, Memory, StopReason};
+
+/// emulated cycles per benchmark iteration
+const CYCLES: usize = 100_000;
+/// instructions per benchmark iteration of the run benchmark
+const INSTRUCTIONS: usize = 10_000;
+
+/// a checksum loop over a page: loads, stores, arithmetic, shifts and a taken branch
+const PROGRAM: [u8; 22] = [
+    0xA2, 0x00,             // $0200 LDX #$00
+    0xBD, 0x00, 0x03,       // $0202 LDA $0300,X
+    0x18,                   // $0205 CLC
+    0x65, 0x10,             // $0206 ADC $10
+    0x85, 0x10,             // $0208 STA $10
+    0x49, 0x5A,             // $020A EOR #$5A
+    0x0A,                   // $020C ASL A
+    0x9D, 0x00, 0x04,       // $020D STA $0400,X
+    0xE8,                   // $0210 INX
+    0xD0, 0xEF,             // $0211 BNE $0202
+    0x4C, 0x00, 0x02,       // $0213 JMP $0200
+];
+
+fn benchmark_cpu(variant: CPUVariant) -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), variant);
+    for (i, byte) in PROGRAM.iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    for i in 0..=0xFF {
+        cpu.write_byte(0x0300 + i, i as u8);
+    }
+    cpu
+}
+
+fn bench_cycles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cycles");
+    group.throughput(Throughput::Elements(CYCLES as u64));
+    for (name, variant, dummy_accesses, block_cache) in [
+        ("nmos", CPUVariant::Nmos6502, true, false),
+        ("nmos_without_dummy_accesses", CPUVariant::Nmos6502, false, false),
+        ("nmos_block_cache", CPUVariant::Nmos6502, true, true),
+        ("65c02", CPUVariant::Wdc65C02, true, false),
+        ("65c816", CPUVariant::Wdc65C816, true, false),
+    ] {
+        let mut cpu: CPU = benchmark_cpu(variant);
+        cpu.set_dummy_accesses(dummy_accesses);
+        cpu.set_block_cache(block_cache);
+        group.bench_function(name, |b| b.iter(|| {
+            assert_eq!(cpu.run_until(black_box(CYCLES)), Ok(StopReason::CycleBudget));
+        }));
+    }
+    group.finish();
+}
+
+fn bench_run(c: &mut Criterion) {
+    let mut group = c.benchmark_group("instructions");
+    group.throughput(Throughput::Elements(INSTRUCTIONS as u64));
+    let mut cpu: CPU = benchmark_cpu(CPUVariant::Nmos6502);
+    group.bench_function("run", |b| b.iter(|| {
+        for _ in 0..INSTRUCTIONS {
+            cpu.run().unwrap();
+        }
+        black_box(cpu.get_state());
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, bench_cycles, bench_run);
+criterion_main!(benches);
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::decode::DecodedOpcode;
+
+/// the cache is emptied when it holds more blocks, e.g. after lots of self-modifying code
+const MAX_BLOCKS: usize = 4096;
+/// longest run of instructions decoded into one block
+pub(crate) const MAX_BLOCK_INSTRUCTIONS: usize = 64;
+/// pages of 256 bytes in the 24-bit address space
+const PAGES: usize = 0x10000;
+/// entries of the direct-mapped index in front of starts
+const RECENT_STARTS: usize = 1024;
+
+/// an instruction of a block and the 24-bit address of its opcode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CachedInstruction {
+    pub(crate) addr: u32,
+    pub(crate) decoded: &'static DecodedOpcode,
+}
+
+/// a straight-line run of decoded instructions
+#[derive(Debug, PartialEq)]
+struct Block {
+    /// shared with run_until while it runs the block
+    instructions: Rc<[CachedInstruction]>,
+    /// first and last byte, the block never wraps around its bank
+    start: u32,
+    end: u32,
+}
+
+/// decoded blocks by the address of their first instruction. blocks are dropped when a write
+/// hits one of their bytes.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BlockCache {
+    /// slots of blocks, None when free
+    blocks: Vec<Option<Block>>,
+    free_slots: Vec<usize>,
+    /// slot of the block starting at an address
+    starts: HashMap<u32, usize>,
+    /// (start, slot) of recently entered blocks by start modulo RECENT_STARTS. branches and jumps
+    /// into a loop find their block here without hashing.
+    recent_starts: Vec<Option<(u32, usize)>>,
+    /// slots of the blocks with bytes in a page
+    pages: HashMap<u32, Vec<usize>>,
+    /// one bit per page holding cached code, spares writes elsewhere the lookup in pages
+    code_pages: Vec<u64>,
+    /// slot and position of the instruction returned last
+    cursor: Option<(usize, usize)>,
+}
+
+impl BlockCache {
+    pub(crate) fn new() -> BlockCache {
+        Self {
+            blocks: Vec::new(),
+            free_slots: Vec::new(),
+            starts: HashMap::new(),
+            recent_starts: vec![None; RECENT_STARTS],
+            pages: HashMap::new(),
+            code_pages: vec![0; PAGES / 64],
+            cursor: None,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// the cached instruction at addr, if the instruction returned last is followed by it, it is
+    /// repeated by tick or a block starts at addr
+    pub(crate) fn instruction_at(&mut self, addr: u32) -> Option<&'static DecodedOpcode> {
+        if let Some((slot, position)) = self.cursor
+            && let Some(block) = &self.blocks[slot] {
+            for position in [position + 1, position] {
+                if let Some(instruction) = block.instructions.get(position)
+                    && instruction.addr == addr {
+                    self.cursor = Some((slot, position));
+                    return Some(instruction.decoded);
+                }
+            }
+        }
+        let slot: usize = self.slot_starting_at(addr)?;
+        self.cursor = Some((slot, 0));
+        self.blocks[slot].as_ref().map(|block| block.instructions[0].decoded)
+    }
+
+    /// the instructions of the block starting at addr
+    pub(crate) fn block_at(&mut self, addr: u32) -> Option<Rc<[CachedInstruction]>> {
+        let slot: usize = self.slot_starting_at(addr)?;
+        self.blocks[slot].as_ref().map(|block| Rc::clone(&block.instructions))
+    }
+
+    fn slot_starting_at(&mut self, addr: u32) -> Option<usize> {
+        let recent: usize = addr as usize % RECENT_STARTS;
+        match self.recent_starts[recent] {
+            Some((start, slot)) if start == addr => Some(slot),
+            _ => {
+                let slot: usize = *self.starts.get(&addr)?;
+                self.recent_starts[recent] = Some((addr, slot));
+                Some(slot)
+            },
+        }
+    }
+
+    /// caches a block ending with the byte at end, its first instruction becomes the one returned last
+    pub(crate) fn insert(&mut self, instructions: Vec<CachedInstruction>, end: u32) {
+        let Some(first) = instructions.first() else {
+            return;
+        };
+        if self.len() >= MAX_BLOCKS {
+            self.clear();
+        }
+        let start: u32 = first.addr;
+        if let Some(slot) = self.starts.get(&start) {
+            self.remove(*slot);
+        }
+
+        let block: Block = Block { instructions: instructions.into(), start, end };
+        let slot: usize = match self.free_slots.pop() {
+            Some(slot) => {
+                self.blocks[slot] = Some(block);
+                slot
+            },
+            None => {
+                self.blocks.push(Some(block));
+                self.blocks.len() - 1
+            },
+        };
+        self.starts.insert(start, slot);
+        self.recent_starts[start as usize % RECENT_STARTS] = Some((start, slot));
+        for page in start >> 8..=end >> 8 {
+            self.pages.entry(page).or_default().push(slot);
+            self.code_pages[page as usize / 64] |= 1 << (page % 64);
+        }
+        self.cursor = Some((slot, 0));
+    }
+
+    /// drops the blocks containing the byte at addr
+    pub(crate) fn invalidate(&mut self, addr: u32) {
+        let page: u32 = (addr >> 8) & (PAGES as u32 - 1);
+        if self.code_pages[page as usize / 64] & (1 << (page % 64)) == 0 {
+            return;
+        }
+        let Some(slots) = self.pages.get(&page) else {
+            return;
+        };
+        let hit: Vec<usize> = slots.iter()
+            .copied()
+            .filter(|slot| self.blocks[*slot].as_ref().is_some_and(|block| (block.start..=block.end).contains(&addr)))
+            .collect();
+        for slot in hit {
+            self.remove(slot);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.blocks.clear();
+        self.free_slots.clear();
+        self.starts.clear();
+        self.recent_starts.fill(None);
+        self.pages.clear();
+        self.code_pages.fill(0);
+        self.cursor = None;
+    }
+
+    fn remove(&mut self, slot: usize) {
+        let Some(block) = self.blocks[slot].take() else {
+            return;
+        };
+        self.starts.remove(&block.start);
+        let recent: usize = block.start as usize % RECENT_STARTS;
+        if self.recent_starts[recent] == Some((block.start, slot)) {
+            self.recent_starts[recent] = None;
+        }
+        for page in block.start >> 8..=block.end >> 8 {
+            let Some(slots) = self.pages.get_mut(&page) else {
+                continue;
+            };
+            slots.retain(|other| *other != slot);
+            if slots.is_empty() {
+                self.pages.remove(&page);
+                self.code_pages[page as usize / 64] &= !(1 << (page % 64));
+            }
+        }
+        self.free_slots.push(slot);
+        if self.cursor.is_some_and(|(cursor_slot, _)| cursor_slot == slot) {
+            self.cursor = None;
+        }
+    }
+}
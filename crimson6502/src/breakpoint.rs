@@ -0,0 +1,53 @@
+use crate::bus::{AccessKind, BusAccess};
+use crate::cpu::RunState;
+
+/// which accesses trigger a watchpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+/// stops run_until after an instruction accessed the inclusive 24-bit range start..=end.
+/// dummy accesses count, as they are visible on the bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub start: u32,
+    pub end: u32,
+    pub kind: WatchKind,
+}
+
+impl Watchpoint {
+    pub fn new(start: u32, end: u32, kind: WatchKind) -> Watchpoint {
+        Self { start, end, kind }
+    }
+
+    /// watches a single address
+    pub fn at(addr: u32, kind: WatchKind) -> Watchpoint {
+        Self::new(addr, addr, kind)
+    }
+
+    pub fn matches(&self, access: &BusAccess) -> bool {
+        let kind_matches: bool = matches!((self.kind, access.kind),
+            (WatchKind::ReadWrite, _) | (WatchKind::Read, AccessKind::Read) | (WatchKind::Write, AccessKind::Write));
+        kind_matches && (self.start..=self.end).contains(&access.addr)
+    }
+}
+
+/// why run_until returned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// the instruction at the 24-bit addr is next and has not run yet
+    Breakpoint { addr: u32 },
+    /// the first access that hit a watchpoint, the instruction performing it has completed
+    Watchpoint { access: BusAccess },
+    /// the cycle budget was used up, possibly exceeded by the last instruction
+    CycleBudget,
+    /// the opcode at addr is unknown or undocumented opcodes are disabled, pc stays on it
+    IllegalOpcode { opcode: u8, addr: u32 },
+    /// a JAM or STP halted the CPU
+    Halted { run_state: RunState, addr: u32 },
+    /// WAI is waiting for an interrupt line to be asserted
+    Waiting { addr: u32 },
+}
@@ -0,0 +1,98 @@
+use crate::memory::Memory;
+
+/// direction of a bus access, the R/W pin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessKind {
+    Read,
+    Write,
+}
+
+/// one bus access as seen on the address and data pins, in the form of visual6502 traces
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BusAccess {
+    pub addr: u32,
+    pub data: u8,
+    pub kind: AccessKind,
+}
+
+impl BusAccess {
+    pub fn read(addr: u32, data: u8) -> BusAccess {
+        Self { addr, data, kind: AccessKind::Read }
+    }
+
+    pub fn write(addr: u32, data: u8) -> BusAccess {
+        Self { addr, data, kind: AccessKind::Write }
+    }
+}
+
+/// everything the CPU is connected to: RAM, ROM and memory-mapped peripherals.
+/// addresses are 24 bits wide, the 6502 variants only use bank 0.
+pub trait Bus {
+    /// reads one byte. reads may have side effects, e.g. clearing a status register.
+    fn read(&mut self, addr: u32) -> u8;
+
+    /// writes one byte.
+    fn write(&mut self, addr: u32, value: u8);
+
+    /// reads one byte without side effects, for debuggers and disassemblers.
+    fn peek(&self, addr: u32) -> u8;
+
+    /// stores the byte peek returns at addr without side effects: no registers are latched and
+    /// no faults raised. step_back restores overwritten bytes with it.
+    /// the default writes, which suits buses whose writes have no side effects.
+    fn poke(&mut self, addr: u32, value: u8) {
+        self.write(addr, value);
+    }
+
+    /// registers latched by writes besides the bytes, e.g. selected banks.
+    /// step_back saves them before every write and restores them with restore_latches.
+    fn latches(&self) -> Vec<u32> {
+        Vec::new()
+    }
+
+    /// restores registers returned by latches
+    fn restore_latches(&mut self, _latches: &[u32]) {}
+
+    /// returns and clears the address of an access the bus rejected since the last call.
+    /// the CPU checks it after every instruction and reports `CpuError::BusFault`.
+    fn take_fault(&mut self) -> Option<u32> {
+        None
+    }
+
+    /// contents a snapshot saves, e.g. RAM, ROM and bank registers.
+    /// buses returning None cannot be saved.
+    fn save_contents(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// restores contents returned by save_contents, returns false if they do not fit this bus
+    fn load_contents(&mut self, _contents: &[u8]) -> bool {
+        false
+    }
+}
+
+impl Bus for Memory {
+    fn read(&mut self, addr: u32) -> u8 {
+        self.read_byte_long(addr)
+    }
+
+    fn write(&mut self, addr: u32, value: u8) {
+        self.write_byte_long(addr, value);
+    }
+
+    fn peek(&self, addr: u32) -> u8 {
+        self.read_byte_long(addr)
+    }
+
+    fn poke(&mut self, addr: u32, value: u8) {
+        self.write_byte_long(addr, value);
+    }
+
+    fn save_contents(&self) -> Option<Vec<u8>> {
+        Some(self.contents().to_vec())
+    }
+
+    fn load_contents(&mut self, contents: &[u8]) -> bool {
+        self.set_contents(contents)
+    }
+}
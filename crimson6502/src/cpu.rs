@@ -1,9 +1,41 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use crate::block_cache::{BlockCache, CachedInstruction, MAX_BLOCK_INSTRUCTIONS};
+use crate::breakpoint::{StopReason, Watchpoint};
+use crate::bus::{AccessKind, Bus, BusAccess};
+use crate::decode::{DecodedOpcode, Handler};
+use crate::error::CpuError;
+use crate::history::{History, HistoryEntry};
 use crate::memory::Memory;
+use crate::observer::{InterruptKind, Observer};
+use crate::snapshot::{Snapshot, SnapshotError};
 use crate::instruction::{AddressingMode, Instruction, Mnemonic};
-use crate::instruction_evaluation::{evaluate_load, evaluate_nop, evaluate_store, evaluate_transfer, InstructionResult};
+use crate::instruction_evaluation::{evaluate_arithmetic, evaluate_bit_manipulation, evaluate_block_move, evaluate_branch, evaluate_compare, evaluate_flag, evaluate_halt, evaluate_increment, evaluate_interrupt, evaluate_jump, evaluate_load, evaluate_logical, evaluate_nop, evaluate_shift, evaluate_stack, evaluate_store, evaluate_transfer, evaluate_undocumented, evaluate_wide, InstructionResult};
 use crate::util::bytes_to_word;
+use crate::variant::CPUVariant;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// base address of the stack, which always lives in page 1
+const STACK_BASE: u16 = 0x0100;
+
+pub const NMI_VECTOR: u16 = 0xFFFA;
+pub const RESET_VECTOR: u16 = 0xFFFC;
+pub const IRQ_VECTOR: u16 = 0xFFFE;
+
+/// 65C816 COP vector in emulation mode
+pub const COP_VECTOR: u16 = 0xFFF4;
+/// 65C816 native mode vectors
+pub const NATIVE_COP_VECTOR: u16 = 0xFFE4;
+pub const NATIVE_BRK_VECTOR: u16 = 0xFFE6;
+pub const NATIVE_NMI_VECTOR: u16 = 0xFFEA;
+pub const NATIVE_IRQ_VECTOR: u16 = 0xFFEE;
+
+/// cycles taken by the hardware interrupt sequence, same as BRK
+const INTERRUPT_CYCLES: u8 = 7;
+
+/// the 6502 registers, widened by the 65C816 ones.
+/// the 65C816 keeps the high bytes of its 16-bit registers separately, so the 6502 registers
+/// stay 8 bits wide and every other variant leaves the extra fields untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CPUState {
     pub a: u8,
     pub x: u8,
@@ -11,22 +43,179 @@ pub struct CPUState {
     pub s: u8,
     pub pc: u16,
     pub sr: u8,
+    /// 65C816 high byte of the accumulator, swapped with A by XBA
+    pub b: u8,
+    /// 65C816 high bytes of the index registers, zero while X is set
+    pub xh: u8,
+    pub yh: u8,
+    /// 65C816 high byte of the stack pointer, fixed to page 1 in emulation mode
+    pub sh: u8,
+    /// 65C816 direct page register, base of the zeropage addressing modes
+    pub d: u16,
+    /// 65C816 data bank register, bank of absolute data addresses
+    pub dbr: u8,
+    /// 65C816 program bank register, bank of PC
+    pub pbr: u8,
+    /// 65C816 emulation flag. set for every other variant, which behave like emulation mode
+    pub e: bool,
 }
 
-#[derive(Debug, PartialEq)]
+/// execution state of the CPU as seen from the embedding API
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunState {
+    Running,
+    /// a JAM opcode locked up the CPU, only a reset recovers
+    Jammed,
+    /// WAI: halted until the IRQ or NMI line is asserted
+    Waiting,
+    /// STP: halted until reset
+    Stopped,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CPUStats {
     total_cycles: usize,
     instructions: usize,
 }
 
+/// everything an instruction changes besides the bus, restored before tick re-runs it and when
+/// stepping back. nmi_pending is recorded but not restored: it is an input latched from outside,
+/// which only servicing the NMI clears.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ExecutionSnapshot {
+    state: CPUState,
+    stats: CPUStats,
+    page_crossed: bool,
+    nmi_pending: bool,
+    run_state: RunState,
+    resumed_breakpoint: Option<u32>,
+}
+
+/// observer callback held back while tick re-runs an instruction, delivered by its last cycle
+#[derive(Debug, PartialEq)]
+enum ObserverEvent {
+    Executed(Instruction, InstructionResult),
+    Interrupt(InterruptKind, CPUState),
+}
+
+/// the interrupt inputs seen by an instruction when it started
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct InterruptInputs {
+    nmi: bool,
+    irq: bool,
+}
+
+/// what a re-run does with its next bus access
+enum ReplayStep {
+    /// an earlier cycle performed the access
+    Replayed(BusAccess),
+    /// the access belongs to the current cycle and goes to the bus
+    Due,
+    /// the access belongs to a later cycle, it is not performed yet
+    Deferred,
+}
+
+/// an instruction being stepped cycle by cycle.
+/// every tick re-runs the instruction from the snapshot: accesses of earlier cycles are answered
+/// from `accesses`, the access of the current cycle goes to the bus and later accesses are
+/// deferred. evaluation is deterministic given the data read, so the re-runs agree with each other.
+#[derive(Debug, PartialEq)]
+struct CycleReplay {
+    start: ExecutionSnapshot,
+    /// accesses performed by the cycles ticked so far
+    accesses: Vec<BusAccess>,
+    /// cycles ticked so far
+    cycles: usize,
+    /// index of the next access of the current re-run
+    position: usize,
+    /// accesses from this index on are deferred
+    limit: usize,
+    /// whether the current re-run deferred an access
+    deferred: bool,
+    /// bus fault raised by an earlier cycle, reported when the instruction completes
+    fault: Option<CpuError>,
+    /// observer callbacks of the current re-run
+    events: Vec<ObserverEvent>,
+    /// interrupt inputs sampled by the first cycle. the re-runs follow them, lines changed
+    /// between ticks are seen by the next instruction.
+    interrupts: Option<InterruptInputs>,
+}
+
+impl CycleReplay {
+    fn new(start: ExecutionSnapshot) -> CycleReplay {
+        Self {
+            start,
+            accesses: Vec::new(),
+            cycles: 0,
+            position: 0,
+            limit: 0,
+            deferred: false,
+            fault: None,
+            events: Vec::new(),
+            interrupts: None,
+        }
+    }
+
+    /// prepares a re-run that performs accesses up to, not including, limit
+    fn begin(&mut self, limit: usize) {
+        self.position = 0;
+        self.limit = limit;
+        self.deferred = false;
+        self.events.clear();
+    }
+
+    fn step(&mut self) -> ReplayStep {
+        let position: usize = self.position;
+        self.position += 1;
+        if let Some(access) = self.accesses.get(position) {
+            ReplayStep::Replayed(*access)
+        } else if position < self.limit {
+            ReplayStep::Due
+        } else {
+            self.deferred = true;
+            ReplayStep::Deferred
+        }
+    }
+}
 
+/// the CPU, connected to a bus. Memory is the plain 64K (or larger) RAM bus.
+/// the observer receives execution events, the default `()` observer ignores them.
 #[derive(Debug, PartialEq)]
-pub struct CPU {
+pub struct CPU<B: Bus = Memory, O: Observer = ()> {
     state: CPUState,
     stats: CPUStats,
-    memory: Memory,
+    bus: B,
+    observer: O,
     /// stores whether last address resolution crossed a page boundary
     page_crossed: bool,
+    /// level of the IRQ input line, serviced while asserted and I is clear
+    irq_line: bool,
+    /// level of the NMI input line, only used to detect the falling edge
+    nmi_line: bool,
+    /// latched NMI edge waiting to be serviced
+    nmi_pending: bool,
+    run_state: RunState,
+    /// whether undocumented opcodes execute, or are rejected like unknown bytes
+    undocumented_opcodes: bool,
+    /// whether the dummy reads and writes of cycles that transfer no data reach the bus
+    dummy_accesses: bool,
+    variant: CPUVariant,
+    /// 24-bit PC breakpoints
+    breakpoints: HashSet<u32>,
+    /// breakpoint reported by the previous run, which lets the next run execute the instruction
+    resumed_breakpoint: Option<u32>,
+    /// bus access watchpoints, checked by run_until
+    watchpoints: Vec<Watchpoint>,
+    /// first access that hit a watchpoint since run_until started
+    watchpoint_hit: Option<BusAccess>,
+    /// every bus access performed, while logging is enabled
+    bus_log: Option<Vec<BusAccess>>,
+    /// instruction partially executed by tick
+    cycle_replay: Option<CycleReplay>,
+    /// executed instructions for stepping back, while enabled
+    history: Option<History>,
+    /// decoded straight-line runs of code, while enabled
+    block_cache: Option<BlockCache>,
 }
 
 impl CPUState {
@@ -38,7 +227,88 @@ impl CPUState {
             s: 0,
             pc: 0,
             sr: 0,
+            b: 0,
+            xh: 0,
+            yh: 0,
+            sh: 0x01,
+            d: 0,
+            dbr: 0,
+            pbr: 0,
+            e: true,
+        }
+    }
+
+    /// 16-bit accumulator C, made of B and A
+    pub fn accumulator(&self) -> u16 {
+        bytes_to_word(self.a, self.b)
+    }
+
+    pub fn set_accumulator(&mut self, value: u16) {
+        self.a = value as u8;
+        self.b = (value >> 8) as u8;
+    }
+
+    /// 16-bit X, the high byte is zero with 8-bit index registers
+    pub fn index_x(&self) -> u16 {
+        bytes_to_word(self.x, self.xh)
+    }
+
+    pub fn set_index_x(&mut self, value: u16) {
+        self.x = value as u8;
+        self.xh = (value >> 8) as u8;
+    }
+
+    /// 16-bit Y, the high byte is zero with 8-bit index registers
+    pub fn index_y(&self) -> u16 {
+        bytes_to_word(self.y, self.yh)
+    }
+
+    pub fn set_index_y(&mut self, value: u16) {
+        self.y = value as u8;
+        self.yh = (value >> 8) as u8;
+    }
+
+    /// 16-bit stack pointer, always in page 1 in emulation mode
+    pub fn stack_pointer(&self) -> u16 {
+        if self.e {
+            STACK_BASE | self.s as u16
+        } else {
+            bytes_to_word(self.s, self.sh)
+        }
+    }
+
+    pub fn set_stack_pointer(&mut self, value: u16) {
+        self.s = value as u8;
+        self.sh = if self.e { 0x01 } else { (value >> 8) as u8 };
+    }
+
+    /// whether the accumulator and memory operations are 16 bits wide
+    pub fn accumulator_16(&self) -> bool {
+        !self.e && self.sr & flag!(memory) == 0
+    }
+
+    /// whether the index registers are 16 bits wide
+    pub fn index_16(&self) -> bool {
+        !self.e && self.sr & flag!(index) == 0
+    }
+
+    /// applies the register widths selected by E, M and X after the status register or E changed.
+    /// 8-bit index registers lose their high bytes, emulation mode forces M, X and page 1 stack.
+    pub(crate) fn update_register_widths(&mut self) {
+        if self.e {
+            self.sr |= flag!(memory) | flag!(index);
+            self.sh = 0x01;
         }
+        if self.sr & flag!(index) != 0 {
+            self.xh = 0;
+            self.yh = 0;
+        }
+    }
+}
+
+impl Default for CPUState {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -49,22 +319,220 @@ impl CPUStats {
             instructions: 0usize,
         }
     }
+
+    pub(crate) fn with_counts(total_cycles: usize, instructions: usize) -> CPUStats {
+        Self {
+            total_cycles,
+            instructions,
+        }
+    }
+
+    /// clock cycles executed, including interrupt sequences
+    pub fn total_cycles(&self) -> usize {
+        self.total_cycles
+    }
+
+    /// instructions executed
+    pub fn instructions(&self) -> usize {
+        self.instructions
+    }
 }
 
-impl CPU {
-    pub fn new(state: CPUState, memory: Memory) -> CPU {
+impl Default for CPUStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Bus> CPU<B> {
+    /// creates an NMOS 6502
+    pub fn new(state: CPUState, bus: B) -> CPU<B> {
+        Self::with_variant(state, bus, CPUVariant::Nmos6502)
+    }
+
+    /// creates a CPU emulating the given member of the 6502 family
+    pub fn with_variant(state: CPUState, bus: B, variant: CPUVariant) -> CPU<B> {
         Self {
-            state: state,
+            state,
             stats: CPUStats::new(),
-            memory: memory,
+            bus,
+            observer: (),
             page_crossed: false,
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+            run_state: RunState::Running,
+            undocumented_opcodes: true,
+            dummy_accesses: true,
+            variant,
+            breakpoints: HashSet::new(),
+            resumed_breakpoint: None,
+            watchpoints: Vec::new(),
+            watchpoint_hit: None,
+            bus_log: None,
+            cycle_replay: None,
+            history: None,
+            block_cache: None,
+        }
+    }
+
+    /// installs an observer, which receives every execution event from now on
+    pub fn with_observer<O: Observer>(self, observer: O) -> CPU<B, O> {
+        CPU {
+            state: self.state,
+            stats: self.stats,
+            bus: self.bus,
+            observer,
+            page_crossed: self.page_crossed,
+            irq_line: self.irq_line,
+            nmi_line: self.nmi_line,
+            nmi_pending: self.nmi_pending,
+            run_state: self.run_state,
+            undocumented_opcodes: self.undocumented_opcodes,
+            dummy_accesses: self.dummy_accesses,
+            variant: self.variant,
+            breakpoints: self.breakpoints,
+            resumed_breakpoint: self.resumed_breakpoint,
+            watchpoints: self.watchpoints,
+            watchpoint_hit: self.watchpoint_hit,
+            bus_log: self.bus_log,
+            cycle_replay: self.cycle_replay,
+            history: self.history,
+            block_cache: self.block_cache,
         }
     }
+}
 
-    /// emulates 6502 reset procedure
+impl<B: Bus, O: Observer> CPU<B, O> {
+    /// emulates 6502 reset procedure.
+    /// the reset sequence performs three suppressed pushes starting from S = 0x00,
+    /// leaving S at 0xFD with interrupts disabled, then loads PC from the reset vector.
+    /// an instruction partially stepped by tick is abandoned, the bytes it already wrote stay in
+    /// the history.
     pub fn init_state(&mut self) {
+        self.end_history_entry();
+        self.cycle_replay = None;
+        self.resumed_breakpoint = None;
         self.state = CPUState::new();
-        // TODO set stack pointer
+        self.state.s = 0xFD;
+        self.state.sr = flag!(interrupt) | flag!(unused);
+        self.state.pc = self.read_word(RESET_VECTOR);
+        self.nmi_pending = false;
+        self.run_state = RunState::Running;
+    }
+
+    /// current execution state
+    pub fn run_state(&self) -> RunState {
+        self.run_state
+    }
+
+    /// the emulated member of the 6502 family
+    pub fn variant(&self) -> CPUVariant {
+        self.variant
+    }
+
+    /// locks up the CPU until the next reset
+    pub(crate) fn jam(&mut self) {
+        self.run_state = RunState::Jammed;
+    }
+
+    /// halts the CPU until an interrupt line is asserted
+    pub(crate) fn wait(&mut self) {
+        self.run_state = RunState::Waiting;
+    }
+
+    /// halts the CPU until the next reset
+    pub(crate) fn stop(&mut self) {
+        self.run_state = RunState::Stopped;
+    }
+
+    /// enables or disables execution of undocumented opcodes.
+    /// when disabled they are treated like bytes that do not decode to an instruction.
+    pub fn set_undocumented_opcodes(&mut self, enabled: bool) {
+        self.undocumented_opcodes = enabled;
+        if let Some(block_cache) = &mut self.block_cache {
+            block_cache.clear();
+        }
+    }
+
+    /// enables or disables the dummy reads and writes the CPU issues in cycles that transfer no
+    /// data, e.g. the read of the un-carried address of an indexed access or the write of the
+    /// unmodified value by a read-modify-write instruction. they only matter for memory-mapped I/O
+    /// with side effects, disabling them is faster. cycle counts are not affected.
+    pub fn set_dummy_accesses(&mut self, enabled: bool) {
+        self.dummy_accesses = enabled;
+    }
+
+    /// enables or disables the block cache. it decodes straight-line runs of code into blocks once
+    /// and executes them without decoding every opcode again. a write to cached code drops the
+    /// blocks holding it, fetched opcodes are compared to the cached ones to notice code the bus
+    /// changed by itself, e.g. by switching banks. run_until runs whole blocks, skipping the
+    /// checks between their instructions while no interrupt is pending and the history is off.
+    /// execution is the same with and without it.
+    pub fn set_block_cache(&mut self, enabled: bool) {
+        self.block_cache = enabled.then(BlockCache::new);
+    }
+
+    /// number of blocks in the block cache
+    pub fn cached_blocks(&self) -> usize {
+        self.block_cache.as_ref().map_or(0, BlockCache::len)
+    }
+
+    /// sets the level of the IRQ line. IRQ is level-triggered: it is serviced before every
+    /// instruction for as long as the line stays asserted and the I flag is clear.
+    pub fn set_irq(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    /// sets the level of the NMI line. NMI is edge-triggered: asserting the line latches one
+    /// interrupt, which is serviced regardless of the I flag.
+    pub fn set_nmi(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = asserted;
+    }
+
+    /// whether an NMI has been latched and not yet serviced
+    pub fn nmi_pending(&self) -> bool {
+        self.nmi_pending
+    }
+
+    /// stops execution with `CpuError::Breakpoint` before the instruction at the 24-bit addr runs
+    pub fn add_breakpoint(&mut self, addr: u32) {
+        self.breakpoints.insert(addr);
+    }
+
+    /// returns whether the breakpoint was set
+    pub fn remove_breakpoint(&mut self, addr: u32) -> bool {
+        self.breakpoints.remove(&addr)
+    }
+
+    pub fn breakpoints(&self) -> &HashSet<u32> {
+        &self.breakpoints
+    }
+
+    /// stops run_until after an instruction accessed the watched range
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+    }
+
+    /// returns whether the watchpoint was set
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        let len: usize = self.watchpoints.len();
+        self.watchpoints.retain(|set| set != watchpoint);
+        self.watchpoints.len() != len
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    /// 24-bit address of the next instruction
+    pub fn program_counter(&self) -> u32 {
+        self.program_address(self.state.pc)
     }
 
     /// get immutable reference to CPUState
@@ -72,6 +540,16 @@ impl CPU {
         &self.state
     }
 
+    /// registers for the evaluators, which update them in place
+    pub(crate) fn state_mut(&mut self) -> &mut CPUState {
+        &mut self.state
+    }
+
+    /// get immutable reference to CPUStats
+    pub fn get_stats(&self) -> &CPUStats {
+        &self.stats
+    }
+
     /// updates whether a page cross occured during last address resolution.
     /// Takes pre- and post-index memory address
     fn update_page_cross(&mut self, addr_unindexed: u16, addr_indexed: u16) {
@@ -79,39 +557,256 @@ impl CPU {
         self.page_crossed = page_crossed;
     }
 
+    /// reads and discards one byte, unless dummy accesses are disabled
+    pub(crate) fn dummy_read(&mut self, addr: u32) {
+        if self.dummy_accesses {
+            self.read_byte_long(addr);
+        }
+    }
+
+    /// reads and discards the byte at S, the cycle before the first pull
+    pub(crate) fn dummy_read_stack(&mut self) {
+        self.dummy_read(self.state.stack_pointer() as u32);
+    }
+
+    /// the extra cycle of an indexed access reads the address before the carry into the high
+    /// byte. the 65C02 re-reads the last operand byte instead.
+    fn dummy_read_uncarried(&mut self, addr_unindexed: u16, index: u16) {
+        let addr: u32 = if self.variant.is_cmos() && !self.variant.is_65816() {
+            self.program_address(self.state.pc.wrapping_sub(1))
+        } else {
+            self.data_address((addr_unindexed & 0xFF00) | (addr_unindexed as u8).wrapping_add(index as u8) as u16)
+        };
+        self.dummy_read(addr);
+    }
+
+    /// the cycle between the read and the write of a read-modify-write instruction.
+    /// the NMOS 6502 and the 65C816 in emulation mode write back the unmodified value, the 65C02
+    /// reads the address again and 65C816 native mode performs an internal operation.
+    fn read_modify_write_dummy_access(&mut self, addr: u32, value: u8) {
+        if !self.dummy_accesses {
+            return;
+        }
+        if !self.variant.is_cmos() || (self.variant.is_65816() && self.state.e) {
+            self.write_byte_long(addr, value);
+        } else if !self.variant.is_65816() {
+            self.read_byte_long(addr);
+        }
+    }
+
     /// whether last address resolution crossed page boundary
     pub fn crossed_page_boundary(&self) -> bool {
         self.page_crossed
     }
 
+    /// immutable reference to the bus
+    pub fn bus(&self) -> &B {
+        &self.bus
+    }
+
+    /// mutable reference to the bus, e.g. to load a program or drive peripherals
+    pub fn bus_mut(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// delivers an observer callback, or holds it back while tick re-runs an instruction
+    fn notify(&mut self, event: ObserverEvent) {
+        if let Some(replay) = &mut self.cycle_replay {
+            replay.events.push(event);
+            return;
+        }
+        match event {
+            ObserverEvent::Executed(instruction, result) => self.observer.after_execute(&instruction, &result),
+            ObserverEvent::Interrupt(kind, state) => self.observer.on_interrupt(kind, &state),
+        }
+    }
+
+    /// delivers the callbacks held back by the re-run that completed an instruction
+    fn deliver_events(&mut self, events: Vec<ObserverEvent>) {
+        for event in events {
+            self.notify(event);
+        }
+    }
+
+    /// starts or stops recording every bus access. stopping discards the log.
+    pub fn set_bus_log(&mut self, enabled: bool) {
+        self.bus_log = if enabled { Some(Vec::new()) } else { None };
+    }
+
+    /// bus accesses recorded since logging was enabled or the log was last taken
+    pub fn bus_log(&self) -> &[BusAccess] {
+        self.bus_log.as_deref().unwrap_or(&[])
+    }
+
+    /// returns and clears the recorded bus accesses
+    pub fn take_bus_log(&mut self) -> Vec<BusAccess> {
+        self.bus_log.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// performs one read on the bus. while tick re-runs an instruction, only the access of the
+    /// current cycle reaches the bus.
+    fn bus_read(&mut self, addr: u32) -> u8 {
+        if let Some(replay) = &mut self.cycle_replay {
+            match replay.step() {
+                ReplayStep::Replayed(access) => return access.data,
+                ReplayStep::Deferred => return self.bus.peek(addr),
+                ReplayStep::Due => (),
+            }
+        }
+        let data: u8 = self.bus.read(addr);
+        self.record_access(BusAccess::read(addr, data));
+        data
+    }
+
+    /// performs one write on the bus, see bus_read
+    fn bus_write(&mut self, addr: u32, value: u8) {
+        if let Some(replay) = &mut self.cycle_replay
+            && !matches!(replay.step(), ReplayStep::Due) {
+            return;
+        }
+        if let Some(history) = &mut self.history {
+            history.record_write(addr, self.bus.peek(addr), self.bus.latches());
+        }
+        if let Some(block_cache) = &mut self.block_cache {
+            block_cache.invalidate(addr);
+        }
+        self.bus.write(addr, value);
+        self.record_access(BusAccess::write(addr, value));
+    }
+
+    fn record_access(&mut self, access: BusAccess) {
+        match access.kind {
+            AccessKind::Read => self.observer.on_read(access.addr, access.data),
+            AccessKind::Write => self.observer.on_write(access.addr, access.data),
+        }
+        if self.watchpoint_hit.is_none() && self.watchpoints.iter().any(|watchpoint| watchpoint.matches(&access)) {
+            self.watchpoint_hit = Some(access);
+        }
+        if let Some(replay) = &mut self.cycle_replay {
+            replay.accesses.push(access);
+        }
+        if let Some(bus_log) = &mut self.bus_log {
+            bus_log.push(access);
+        }
+    }
+
     /// writes one byte to memory at given address
     pub fn write_byte(&mut self, addr: u16, value: u8) {
-        self.memory.write_byte(addr, value);
+        self.bus_write(addr as u32, value);
     }
 
     /// reads one byte from memory at given address
-    pub fn read_byte(&self, addr: u16) -> u8 {
-        let read_byte: u8 = self.memory.read_byte(addr);
+    pub fn read_byte(&mut self, addr: u16) -> u8 {
+        let read_byte: u8 = self.bus_read(addr as u32);
         read_byte
     }
 
+    /// writes one byte to memory at given 24-bit address
+    pub fn write_byte_long(&mut self, addr: u32, value: u8) {
+        self.bus_write(addr, value);
+    }
+
+    /// reads one byte from memory at given 24-bit address
+    pub fn read_byte_long(&mut self, addr: u32) -> u8 {
+        let read_byte: u8 = self.bus_read(addr);
+        read_byte
+    }
+
+    /// reads one byte at given address without side effects on the bus
+    pub fn peek_byte(&self, addr: u16) -> u8 {
+        self.bus.peek(addr as u32)
+    }
+
+    /// reads one byte at given 24-bit address without side effects on the bus
+    pub fn peek_byte_long(&self, addr: u32) -> u8 {
+        self.bus.peek(addr)
+    }
+
+    /// reads one word (little endian) at given address without side effects on the bus
+    pub fn peek_word(&self, addr: u16) -> u16 {
+        bytes_to_word(self.peek_byte(addr), self.peek_byte(addr.wrapping_add(1)))
+    }
+
+    /// reads one word from memory (little endian) starting at the 24-bit addr.
+    /// the high byte may be in the next bank.
+    pub fn read_word_long(&mut self, addr: u32) -> u16 {
+        let lo: u8 = self.read_byte_long(addr);
+        let hi: u8 = self.read_byte_long(addr.wrapping_add(1) & 0xFF_FFFF);
+        let read_word: u16 = bytes_to_word(lo, hi);
+        read_word
+    }
+
+    /// writes one word to memory (little endian) starting at the 24-bit addr.
+    pub fn write_word_long(&mut self, addr: u32, value: u16) {
+        self.write_byte_long(addr, value as u8);
+        self.write_byte_long(addr.wrapping_add(1) & 0xFF_FFFF, (value >> 8) as u8);
+    }
+
     /// reads one word from memory (little endian) starting at addr.
-    pub fn read_word(&self, addr: u16) -> u16 {
-        let lo: u8 = self.memory.read_byte(addr);
-        let hi: u8 = self.memory.read_byte(addr.wrapping_add(1));
+    pub fn read_word(&mut self, addr: u16) -> u16 {
+        let lo: u8 = self.read_byte(addr);
+        let hi: u8 = self.read_byte(addr.wrapping_add(1));
         let read_word: u16 = bytes_to_word(lo, hi);
         read_word
     }
 
     /// reads one word from zero-page memory (little endian) starting at addr.
     /// wraps zero page
-    pub fn read_word_zp(&self, addr: u8) -> u16 {
-        let lo: u8 = self.memory.read_byte(addr as u16);
-        let hi: u8 = self.memory.read_byte((addr.wrapping_add(1)) as u16);
+    pub fn read_word_zp(&mut self, addr: u8) -> u16 {
+        let lo: u8 = self.read_byte(addr as u16);
+        let hi: u8 = self.read_byte((addr.wrapping_add(1)) as u16);
         let read_word: u16 = bytes_to_word(lo, hi);
         read_word
     }
 
+    /// reads one word from memory (little endian) starting at addr.
+    /// the high byte is read from the same page as the low byte, reproducing the NMOS JMP ($xxFF) bug.
+    pub fn read_word_page_wrapped(&mut self, addr: u16) -> u16 {
+        let lo: u8 = self.read_byte(addr);
+        let hi: u8 = self.read_byte((addr & 0xFF00) | (addr.wrapping_add(1) & 0x00FF));
+        let read_word: u16 = bytes_to_word(lo, hi);
+        read_word
+    }
+
+    /// writes one byte to the stack at $0100+S, then decrements S.
+    /// S wraps around inside page 1, except in 65C816 native mode where it is 16 bits wide.
+    pub fn push_byte(&mut self, value: u8) {
+        let stack_pointer: u16 = self.state.stack_pointer();
+        self.write_byte(stack_pointer, value);
+        self.state.set_stack_pointer(stack_pointer.wrapping_sub(1));
+    }
+
+    /// increments S, then reads one byte from the stack at $0100+S.
+    /// S wraps around inside page 1, except in 65C816 native mode where it is 16 bits wide.
+    pub fn pull_byte(&mut self) -> u8 {
+        let stack_pointer: u16 = self.state.stack_pointer().wrapping_add(1);
+        self.state.set_stack_pointer(stack_pointer);
+        let pulled_byte: u8 = self.read_byte(self.state.stack_pointer());
+        pulled_byte
+    }
+
+    /// pushes one word onto the stack, high byte first.
+    pub fn push_word(&mut self, value: u16) {
+        self.push_byte((value >> 8) as u8);
+        self.push_byte(value as u8);
+    }
+
+    /// pulls one word from the stack, low byte first.
+    pub fn pull_word(&mut self) -> u16 {
+        let lo: u8 = self.pull_byte();
+        let hi: u8 = self.pull_byte();
+        let pulled_word: u16 = bytes_to_word(lo, hi);
+        pulled_word
+    }
+
     /// fetches pc and then increments it
     pub fn fetch_operand_address(&mut self) -> u16 {
         let fetched_operand_address: u16 = self.state.pc;
@@ -119,10 +814,11 @@ impl CPU {
         fetched_operand_address
     }
 
-    /// fetches byte pointed to by pc and then increments pc
+    /// fetches byte pointed to by pc in the program bank and then increments pc.
+    /// pc wraps around inside the program bank.
     pub fn fetch_byte(&mut self) -> u8 {
         let operand_address: u16 = self.fetch_operand_address();
-        let fetched_byte: u8 = self.memory.read_byte(operand_address);
+        let fetched_byte: u8 = self.read_byte_long(self.program_address(operand_address));
         fetched_byte
     }
 
@@ -135,95 +831,896 @@ impl CPU {
         fetched_word
     }
 
-    /// run a single instruction cycle
-    pub fn run(&mut self) {
-        let instruction_byte: u8 = self.fetch_byte();
-        let instruction_option: Option<Instruction> = Instruction::from_byte(instruction_byte);
+    /// fetches a 24-bit address, low byte first, increments pc three times.
+    pub fn fetch_long(&mut self) -> u32 {
+        let word: u16 = self.fetch_word();
+        let bank: u8 = self.fetch_byte();
+        ((bank as u32) << 16) | word as u32
+    }
+
+    /// 24-bit address of addr in the program bank
+    fn program_address(&self, addr: u16) -> u32 {
+        ((self.state.pbr as u32) << 16) | addr as u32
+    }
+
+    /// 24-bit address of addr in the data bank
+    fn data_address(&self, addr: u16) -> u32 {
+        ((self.state.dbr as u32) << 16) | addr as u32
+    }
+
+    /// 24-bit address of offset in the direct page, which is always in bank 0.
+    /// the zeropage of the 6502 is a direct page at 0, where indexing wraps around inside the page.
+    /// the 65C816 only keeps this wrap in emulation mode with a page aligned direct page.
+    fn direct_address(&self, offset: u16) -> u32 {
+        let addr: u16 = if self.state.e && self.state.d & 0x00FF == 0 {
+            self.state.d | (offset & 0x00FF)
+        } else {
+            self.state.d.wrapping_add(offset)
+        };
+        addr as u32
+    }
+
+    /// reads a pointer from the direct page at offset.
+    /// both bytes go through direct_address, so the pointer wraps inside the zeropage where indexing does.
+    fn read_direct_word(&mut self, offset: u16) -> u16 {
+        let lo: u8 = self.read_byte_long(self.direct_address(offset));
+        let hi: u8 = self.read_byte_long(self.direct_address(offset.wrapping_add(1)));
+        bytes_to_word(lo, hi)
+    }
 
-        if let Some(instruction) = instruction_option {
-            let result: InstructionResult = self.execute_instruction(instruction);
+    /// maps an emulation mode vector to its 65C816 native mode counterpart
+    fn native_vector(vector: u16, brk: bool) -> u16 {
+        match vector {
+            NMI_VECTOR => NATIVE_NMI_VECTOR,
+            COP_VECTOR => NATIVE_COP_VECTOR,
+            _ if brk => NATIVE_BRK_VECTOR,
+            _ => NATIVE_IRQ_VECTOR,
+        }
+    }
+
+    /// pushes PC and status register, sets I and loads PC from the given vector.
+    /// `brk` selects whether B is set in the pushed status register.
+    /// in 65C816 native mode the program bank is pushed as well, P is pushed as is and the
+    /// native vector corresponding to `vector` is used.
+    pub fn enter_interrupt(&mut self, vector: u16, brk: bool) {
+        let kind: InterruptKind = match vector {
+            _ if brk => InterruptKind::Brk,
+            NMI_VECTOR => InterruptKind::Nmi,
+            COP_VECTOR => InterruptKind::Cop,
+            _ => InterruptKind::Irq,
+        };
+        self.notify(ObserverEvent::Interrupt(kind, self.state));
+        let native: bool = !self.state.e;
+        let pushed_sr: u8 = if native {
+            self.state.sr
+        } else if brk {
+            self.state.sr | flag!(brk) | flag!(unused)
+        } else {
+            (self.state.sr & !flag!(brk)) | flag!(unused)
+        };
+        if native {
+            self.push_byte(self.state.pbr);
+        }
+        self.push_word(self.state.pc);
+        self.push_byte(pushed_sr);
+        self.state.sr |= flag!(interrupt);
+        // the 65C02 also leaves decimal mode when entering an interrupt
+        if self.variant.is_cmos() {
+            self.state.sr &= !flag!(decimal);
+        }
+        self.state.pbr = 0;
+        self.state.pc = if native {
+            self.read_word(Self::native_vector(vector, brk))
+        } else {
+            self.read_word(vector)
+        };
+    }
 
-            #[cfg(debug_assertions)]
-            println!("{:?}, cycles: {:?}, instruction length: {:?}", result.state, result.cycles, result.instruction_length);
+    /// samples the interrupt inputs once per instruction. a latched NMI is always serviced, so
+    /// sampling it clears the latch. re-runs by tick get the inputs of the first cycle.
+    fn sample_interrupts(&mut self) -> InterruptInputs {
+        if let Some(interrupts) = self.cycle_replay.as_ref().and_then(|replay| replay.interrupts) {
+            return interrupts;
+        }
+        let interrupts: InterruptInputs = InterruptInputs { nmi: self.nmi_pending, irq: self.irq_line };
+        self.nmi_pending = false;
+        if let Some(replay) = &mut self.cycle_replay {
+            replay.interrupts = Some(interrupts);
+        }
+        interrupts
+    }
 
-            self.state = result.state;
-            self.stats.total_cycles += result.cycles as usize;
-            self.stats.instructions += 1;
+    /// services a sampled NMI or an asserted, unmasked IRQ.
+    /// returns whether an interrupt sequence was run.
+    fn poll_interrupts(&mut self, interrupts: InterruptInputs) -> bool {
+        let vector: u16 = if interrupts.nmi {
+            NMI_VECTOR
+        } else if interrupts.irq && self.state.sr & flag!(interrupt) == 0 {
+            IRQ_VECTOR
         } else {
-            panic!("Could not convert byte to instruction: {:?} at address {:?}.", instruction_byte, self.state.pc);
+            return false;
+        };
+
+        // native mode spends a cycle pushing the program bank
+        let cycles: u8 = INTERRUPT_CYCLES + !self.state.e as u8;
+        // the sequence starts like BRK, fetching the opcode and the padding byte without
+        // incrementing PC
+        self.dummy_read(self.program_counter());
+        self.dummy_read(self.program_counter());
+        self.enter_interrupt(vector, false);
+        self.stats.total_cycles += cycles as usize;
+        true
+    }
+
+    /// run a single instruction cycle.
+    /// if an interrupt is pending, the interrupt sequence runs in place of the next instruction.
+    /// a waiting CPU does nothing until an interrupt line is asserted. an IRQ masked by I ends the
+    /// wait without being serviced.
+    /// a jammed or stopped CPU returns `CpuError::Halted`, as does the instruction that halts it.
+    /// a breakpoint is reported once, running again executes the instruction.
+    /// a bus fault is reported after the faulting instruction completed.
+    /// an instruction partially stepped by tick is completed instead.
+    pub fn run(&mut self) -> Result<(), CpuError> {
+        let result: Result<(), CpuError> = match self.cycle_replay.take() {
+            Some(replay) => self.finish_instruction(replay),
+            None => {
+                self.begin_history_entry();
+                self.execute_next()
+            },
+        };
+        self.end_history_entry();
+        result
+    }
+
+    /// runs instructions until a breakpoint, watchpoint, illegal opcode or halt stops the CPU, or
+    /// `max_cycles` have been spent. the budget is checked between instructions, so the last
+    /// instruction may exceed it. a CPU stopped at a breakpoint resumes past it.
+    /// other errors, e.g. bus faults, are returned as errors.
+    pub fn run_until(&mut self, max_cycles: usize) -> Result<StopReason, CpuError> {
+        let start_cycles: usize = self.stats.total_cycles;
+        self.watchpoint_hit = None;
+        loop {
+            if self.stats.total_cycles - start_cycles >= max_cycles {
+                return Ok(StopReason::CycleBudget);
+            }
+            // nothing can assert a line while running, a waiting CPU would wait forever
+            if self.run_state == RunState::Waiting && !self.nmi_pending && !self.irq_line {
+                return Ok(StopReason::Waiting { addr: self.program_counter() });
+            }
+            let result: Result<(), CpuError> = match self.can_run_block() {
+                true => match self.run_block(start_cycles.saturating_add(max_cycles)) {
+                    Ok(true) => Ok(()),
+                    Ok(false) => self.run(),
+                    Err(error) => Err(error),
+                },
+                false => self.run(),
+            };
+            match result {
+                Ok(()) => (),
+                Err(CpuError::Breakpoint { addr }) => return Ok(StopReason::Breakpoint { addr }),
+                Err(CpuError::IllegalOpcode { opcode, addr }) => return Ok(StopReason::IllegalOpcode { opcode, addr }),
+                Err(CpuError::Halted { run_state, addr }) => return Ok(StopReason::Halted { run_state, addr }),
+                Err(error) => return Err(error),
+            }
+            if let Some(access) = self.watchpoint_hit.take() {
+                return Ok(StopReason::Watchpoint { access });
+            }
+        }
+    }
+
+    /// whether run_until may run a cached block at once: besides the instructions nothing can
+    /// raise an interrupt while it runs, so no interrupt is polled between them, and neither
+    /// tick nor the history need to see every instruction
+    fn can_run_block(&self) -> bool {
+        self.block_cache.is_some()
+            && self.run_state == RunState::Running
+            && !self.nmi_pending
+            && !self.irq_line
+            && self.cycle_replay.is_none()
+            && self.history.is_none()
+            && self.resumed_breakpoint.is_none()
+    }
+
+    /// runs the instructions of the cached block starting at PC until one of them leaves the
+    /// block, a breakpoint or watchpoint is hit, the CPU halts or the cycle count reaches
+    /// `end_cycles`. every fetched opcode is compared with the cached one, so code changed since
+    /// the block was decoded runs as it is now. returns whether an instruction ran.
+    fn run_block(&mut self, end_cycles: usize) -> Result<bool, CpuError> {
+        let pc: u32 = self.program_counter();
+        let Some(instructions) = self.block_cache.as_mut().and_then(|block_cache| block_cache.block_at(pc)) else {
+            return Ok(false);
+        };
+        let mut executed: bool = false;
+        for cached in instructions.iter() {
+            let instruction_addr: u32 = self.program_counter();
+            let stop: bool = instruction_addr != cached.addr
+                || self.stats.total_cycles >= end_cycles
+                || self.run_state != RunState::Running
+                || self.watchpoint_hit.is_some()
+                || (!self.breakpoints.is_empty() && self.breakpoints.contains(&instruction_addr));
+            if stop {
+                break;
+            }
+            self.observer.before_fetch(instruction_addr, &self.state);
+            let instruction_byte: u8 = self.fetch_byte();
+            let decoded: Option<&'static DecodedOpcode> = match instruction_byte == cached.decoded.instruction.opcode {
+                true => Some(cached.decoded),
+                false => self.cached_opcode(instruction_addr, instruction_byte),
+            };
+            executed = true;
+            self.execute_decoded(instruction_addr, instruction_byte, decoded)?;
+        }
+        Ok(executed)
+    }
+
+    /// advances by exactly one clock cycle, performing the bus access of that cycle.
+    /// cycles without a bus access are internal operations. registers, stats and the run state
+    /// are updated by the last cycle of the instruction, which is also when errors are reported,
+    /// except breakpoints and illegal opcodes, which are reported by the first cycle.
+    pub fn tick(&mut self) -> Result<(), CpuError> {
+        if !self.instruction_in_progress() {
+            self.begin_history_entry();
+        }
+        let result: Result<(), CpuError> = self.tick_cycle();
+        if !self.instruction_in_progress() {
+            self.end_history_entry();
+        }
+        result
+    }
+
+    fn tick_cycle(&mut self) -> Result<(), CpuError> {
+        let mut replay: CycleReplay = match self.cycle_replay.take() {
+            Some(replay) => replay,
+            None => CycleReplay::new(self.execution_snapshot()),
+        };
+        self.restore_execution_snapshot(&replay.start);
+        replay.cycles += 1;
+        replay.begin(replay.accesses.len() + 1);
+        self.cycle_replay = Some(replay);
+        let result: Result<(), CpuError> = self.execute_next();
+        let Some(mut replay) = self.cycle_replay.take() else {
+            return result;
+        };
+
+        match result {
+            // the instruction did not start
+            Err(CpuError::Breakpoint { .. } | CpuError::IllegalOpcode { .. }
+                | CpuError::UnsupportedMnemonic(_) | CpuError::UnsupportedAddressingMode(_)) => return result,
+            Err(fault @ CpuError::BusFault { .. }) => replay.fault = Some(fault),
+            _ => (),
+        }
+
+        let cycles: usize = self.stats.total_cycles - replay.start.stats.total_cycles;
+        if replay.cycles < cycles {
+            self.restore_execution_snapshot(&replay.start);
+            self.cycle_replay = Some(replay);
+            return Ok(());
+        }
+        if replay.deferred {
+            // more accesses than cycles, the last cycle performs the rest
+            return self.finish_instruction(replay);
+        }
+        self.deliver_events(std::mem::take(&mut replay.events));
+        match (result, replay.fault) {
+            (Ok(()), Some(fault)) => Err(fault),
+            (result, _) => result,
+        }
+    }
+
+    /// whether tick stopped in the middle of an instruction
+    pub fn instruction_in_progress(&self) -> bool {
+        self.cycle_replay.is_some()
+    }
+
+    /// keeps the last `capacity` executed instructions, with the registers before each one and
+    /// the bytes it overwrote, so that step_back can undo them. 0 disables the history.
+    /// memory is restored with `Bus::poke` and `Bus::restore_latches`, which undo writes without
+    /// their side effects. peripherals are rewound as far as their bus saves their latches.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        match &mut self.history {
+            _ if capacity == 0 => self.history = None,
+            Some(history) => history.set_capacity(capacity),
+            None => self.history = Some(History::new(capacity)),
+        }
+    }
+
+    /// number of instructions step_back can undo
+    pub fn history_len(&self) -> usize {
+        self.history.as_ref().map_or(0, History::len)
+    }
+
+    /// undoes up to `steps` executed instructions or interrupt sequences, newest first, and
+    /// returns how many were undone. an instruction partially stepped by tick is abandoned first,
+    /// without counting as a step.
+    pub fn step_back(&mut self, steps: usize) -> usize {
+        if let Some(entry) = self.history.as_mut().and_then(History::take_current) {
+            self.undo(entry);
+        }
+        let mut undone: usize = 0;
+        while undone < steps && let Some(entry) = self.history.as_mut().and_then(History::pop) {
+            self.undo(entry);
+            undone += 1;
+        }
+        undone
+    }
+
+    /// steps back until the next instruction is at the 24-bit addr, and returns the number of
+    /// steps. returns None if the history runs out first, leaving the CPU at the oldest
+    /// recorded instruction.
+    pub fn step_back_until(&mut self, addr: u32) -> Option<usize> {
+        let mut undone: usize = 0;
+        loop {
+            if self.step_back(1) == 0 {
+                return None;
+            }
+            undone += 1;
+            if self.program_counter() == addr {
+                return Some(undone);
+            }
+        }
+    }
+
+    fn undo(&mut self, entry: HistoryEntry) {
+        // latches first, the byte goes to the bank that was mapped when it was overwritten
+        for overwritten in entry.overwritten.into_iter().rev() {
+            if let Some(block_cache) = &mut self.block_cache {
+                block_cache.invalidate(overwritten.addr);
+            }
+            self.bus.restore_latches(&overwritten.latches);
+            self.bus.poke(overwritten.addr, overwritten.value);
+        }
+        self.restore_execution_snapshot(&entry.before);
+        // an undone NMI is pending again, an NMI latched since stays pending
+        self.nmi_pending |= entry.before.nmi_pending;
+        self.cycle_replay = None;
+    }
+
+    fn begin_history_entry(&mut self) {
+        if self.history.is_none() {
+            return;
+        }
+        let before: ExecutionSnapshot = self.execution_snapshot();
+        if let Some(history) = &mut self.history {
+            history.begin(before);
+        }
+    }
+
+    fn end_history_entry(&mut self) {
+        let total_cycles: usize = self.stats.total_cycles;
+        if let Some(history) = &mut self.history {
+            history.end(|before| before.stats.total_cycles != total_cycles);
+        }
+    }
+
+    /// completes an instruction stepped by tick, performing all of its remaining accesses
+    fn finish_instruction(&mut self, mut replay: CycleReplay) -> Result<(), CpuError> {
+        self.restore_execution_snapshot(&replay.start);
+        replay.begin(usize::MAX);
+        self.cycle_replay = Some(replay);
+        let result: Result<(), CpuError> = self.execute_next();
+        let Some(replay) = self.cycle_replay.take() else {
+            return result;
+        };
+        self.deliver_events(replay.events);
+        match (result, replay.fault) {
+            (Ok(()), Some(fault)) => Err(fault),
+            (result, _) => result,
+        }
+    }
+
+    /// captures the machine for a save state. fails while tick is in the middle of an instruction
+    /// or if the bus cannot be saved.
+    pub fn snapshot(&self) -> Result<Snapshot, SnapshotError> {
+        if self.instruction_in_progress() {
+            return Err(SnapshotError::InstructionInProgress);
+        }
+        let bus: Vec<u8> = self.bus.save_contents().ok_or(SnapshotError::UnsupportedBus)?;
+        Ok(Snapshot {
+            variant: self.variant,
+            state: self.state,
+            stats: self.stats,
+            run_state: self.run_state,
+            page_crossed: self.page_crossed,
+            irq_line: self.irq_line,
+            nmi_line: self.nmi_line,
+            nmi_pending: self.nmi_pending,
+            undocumented_opcodes: self.undocumented_opcodes,
+            dummy_accesses: self.dummy_accesses,
+            bus,
+        })
+    }
+
+    /// returns the machine to a snapshot, including the variant. breakpoints are kept, an
+    /// instruction partially stepped by tick is abandoned and the history is cleared. nothing
+    /// changes if the bus rejects the saved contents.
+    pub fn restore_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        if !self.bus.load_contents(&snapshot.bus) {
+            return Err(SnapshotError::UnsupportedBus);
+        }
+        self.variant = snapshot.variant;
+        self.state = snapshot.state;
+        self.stats = snapshot.stats;
+        self.run_state = snapshot.run_state;
+        self.page_crossed = snapshot.page_crossed;
+        self.irq_line = snapshot.irq_line;
+        self.nmi_line = snapshot.nmi_line;
+        self.nmi_pending = snapshot.nmi_pending;
+        self.undocumented_opcodes = snapshot.undocumented_opcodes;
+        self.dummy_accesses = snapshot.dummy_accesses;
+        self.resumed_breakpoint = None;
+        self.cycle_replay = None;
+        if let Some(block_cache) = &mut self.block_cache {
+            block_cache.clear();
+        }
+        // the recorded deltas lead to the replaced machine
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+        Ok(())
+    }
+
+    /// snapshot in the versioned binary format
+    pub fn save_state(&self) -> Result<Vec<u8>, SnapshotError> {
+        Ok(self.snapshot()?.to_bytes())
+    }
+
+    /// restores a snapshot in the binary format
+    pub fn load_state(&mut self, bytes: &[u8]) -> Result<(), SnapshotError> {
+        self.restore_snapshot(&Snapshot::from_bytes(bytes)?)
+    }
+
+    /// snapshot as JSON
+    pub fn save_state_json(&self) -> Result<String, SnapshotError> {
+        Ok(self.snapshot()?.to_json())
+    }
+
+    /// restores a snapshot in JSON
+    pub fn load_state_json(&mut self, json: &str) -> Result<(), SnapshotError> {
+        self.restore_snapshot(&Snapshot::from_json(json)?)
+    }
+
+    fn execution_snapshot(&self) -> ExecutionSnapshot {
+        ExecutionSnapshot {
+            state: self.state,
+            stats: self.stats,
+            page_crossed: self.page_crossed,
+            nmi_pending: self.nmi_pending,
+            run_state: self.run_state,
+            resumed_breakpoint: self.resumed_breakpoint,
+        }
+    }
+
+    fn restore_execution_snapshot(&mut self, snapshot: &ExecutionSnapshot) {
+        self.state = snapshot.state;
+        self.stats = snapshot.stats;
+        self.page_crossed = snapshot.page_crossed;
+        self.run_state = snapshot.run_state;
+        self.resumed_breakpoint = snapshot.resumed_breakpoint;
+    }
+
+    fn execute_next(&mut self) -> Result<(), CpuError> {
+        if matches!(self.run_state, RunState::Jammed | RunState::Stopped) {
+            return Err(self.halted());
+        }
+        let interrupts: InterruptInputs = self.sample_interrupts();
+        if self.run_state == RunState::Waiting {
+            if !interrupts.nmi && !interrupts.irq {
+                return Ok(());
+            }
+            self.run_state = RunState::Running;
         }
+        if self.poll_interrupts(interrupts) {
+            return Ok(());
+        }
+
+        let instruction_addr: u32 = self.program_counter();
+        if self.breakpoints.contains(&instruction_addr) && self.resumed_breakpoint.take() != Some(instruction_addr) {
+            self.resumed_breakpoint = Some(instruction_addr);
+            return Err(CpuError::Breakpoint { addr: instruction_addr });
+        }
+
+        // tick re-runs the instruction every cycle, only the first cycle fetches
+        if self.cycle_replay.as_ref().is_none_or(|replay| replay.cycles == 1 && replay.limit == 1) {
+            self.observer.before_fetch(instruction_addr, &self.state);
+        }
+        let instruction_byte: u8 = self.fetch_byte();
+        let decoded: Option<&'static DecodedOpcode> = match self.block_cache {
+            Some(_) => self.cached_opcode(instruction_addr, instruction_byte),
+            None => self.decode_opcode(instruction_byte),
+        };
+        self.execute_decoded(instruction_addr, instruction_byte, decoded)
+    }
+
+    /// executes the instruction whose opcode was just fetched from instruction_addr
+    fn execute_decoded(&mut self, instruction_addr: u32, instruction_byte: u8, decoded: Option<&'static DecodedOpcode>) -> Result<(), CpuError> {
+        let Some(decoded) = decoded else {
+            // leave pc on the opcode so the error can be inspected and is reported again
+            self.state.pc = self.state.pc.wrapping_sub(1);
+            return Err(CpuError::IllegalOpcode { opcode: instruction_byte, addr: instruction_addr });
+        };
+        let instruction: Instruction = decoded.instruction;
+
+        // single byte instructions read the byte after the opcode without incrementing PC.
+        // BRK fetches its padding byte instead, JAM locks up the bus
+        if matches!(instruction.addressing_mode, AddressingMode::Implied | AddressingMode::Accumulator)
+            && !matches!(instruction.mnemonic, Mnemonic::BRK | Mnemonic::JAM) {
+            self.dummy_read(self.program_counter());
+        }
+
+        let direct_page_cycles: u8 = self.direct_page_cycles(instruction.addressing_mode);
+        let wide: bool = !self.state.e && self.is_16_bit_operation(&instruction);
+        let cycles: u8 = self.execute_instruction(decoded, wide)? + direct_page_cycles;
+
+        // immediate operands of 16-bit operations are two bytes long
+        let length: u8 = decoded.length + (wide && instruction.addressing_mode == AddressingMode::Immediate) as u8;
+        self.notify(ObserverEvent::Executed(instruction, InstructionResult::new(self.state, cycles, length)));
+
+        self.stats.total_cycles += cycles as usize;
+        self.stats.instructions += 1;
+
+        if let Some(addr) = self.bus.take_fault() {
+            return Err(CpuError::BusFault { addr });
+        }
+        match self.run_state {
+            RunState::Jammed | RunState::Stopped => Err(self.halted()),
+            _ => Ok(()),
+        }
+    }
+
+    /// the instruction an opcode decodes to, unless it is unknown or a disabled undocumented opcode
+    fn decode_opcode(&self, opcode: u8) -> Option<&'static DecodedOpcode> {
+        DecodedOpcode::from_byte(opcode, self.variant)
+            .filter(|decoded| self.undocumented_opcodes || !decoded.instruction.undocumented)
     }
 
-    fn execute_instruction(&mut self, instruction: Instruction) -> InstructionResult {
+    /// decodes the opcode fetched from addr through the block cache. a new block is decoded
+    /// unless a cached one continues at addr with the same opcode.
+    fn cached_opcode(&mut self, addr: u32, opcode: u8) -> Option<&'static DecodedOpcode> {
+        let block_cache: &mut BlockCache = self.block_cache.as_mut()?;
+        match block_cache.instruction_at(addr) {
+            Some(decoded) if decoded.instruction.opcode == opcode => return Some(decoded),
+            Some(_) => block_cache.invalidate(addr),
+            None => (),
+        }
+
+        let (instructions, end) = self.decode_block(addr, opcode);
+        let first: Option<&'static DecodedOpcode> = instructions.first().map(|instruction| instruction.decoded);
+        if let Some(block_cache) = &mut self.block_cache {
+            block_cache.insert(instructions, end);
+        }
+        first
+    }
+
+    /// decodes the straight-line run of code at addr, which starts with the fetched opcode, and
+    /// returns it with the address of its last byte. the run ends after an instruction that may
+    /// continue elsewhere or change the register widths, before an unknown or disabled opcode and
+    /// before the end of the bank. the registers widths cannot change in between, so 16-bit
+    /// immediates are decoded with their actual length.
+    fn decode_block(&self, addr: u32, opcode: u8) -> (Vec<CachedInstruction>, u32) {
+        let mut instructions: Vec<CachedInstruction> = Vec::new();
+        let mut addr: u32 = addr;
+        let mut opcode: u8 = opcode;
+        let mut end: u32 = addr;
+        while instructions.len() < MAX_BLOCK_INSTRUCTIONS {
+            let Some(decoded) = self.decode_opcode(opcode) else {
+                break;
+            };
+            let instruction: Instruction = decoded.instruction;
+            let wide: bool = !self.state.e && self.is_16_bit_operation(&instruction);
+            let length: u32 = decoded.length as u32 + (wide && instruction.addressing_mode == AddressingMode::Immediate) as u32;
+            if (addr & 0xFFFF) + length > 0xFFFF {
+                break;
+            }
+            instructions.push(CachedInstruction { addr, decoded });
+            end = addr + length;
+
+            let continues_elsewhere: bool = matches!(decoded.handler,
+                Handler::Branch | Handler::Jump | Handler::Interrupt | Handler::Halt | Handler::BlockMove)
+                || matches!(instruction.mnemonic,
+                    Mnemonic::BBR | Mnemonic::BBS | Mnemonic::JAM | Mnemonic::REP | Mnemonic::SEP | Mnemonic::XCE | Mnemonic::PLP);
+            if continues_elsewhere || end & 0xFFFF == 0xFFFF {
+                break;
+            }
+            addr = end + 1;
+            opcode = self.bus.peek(addr);
+        }
+        (instructions, end)
+    }
+
+    fn halted(&self) -> CpuError {
+        CpuError::Halted { run_state: self.run_state, addr: self.program_counter() }
+    }
+
+    /// the 65C816 spends a cycle on direct page accesses while the direct page is not page aligned
+    fn direct_page_cycles(&self, addressing_mode: AddressingMode) -> u8 {
+        let direct_page_mode: bool = matches!(addressing_mode,
+            AddressingMode::ZeroPage | AddressingMode::ZeroPageX | AddressingMode::ZeroPageY
+            | AddressingMode::IndirectX | AddressingMode::IndirectY | AddressingMode::ZeroPageIndirect
+            | AddressingMode::ZeroPageIndirectLong | AddressingMode::ZeroPageIndirectLongY);
+        (direct_page_mode && self.state.d & 0x00FF != 0) as u8
+    }
+
+    /// whether the instruction operates on a 16-bit accumulator or 16-bit index registers,
+    /// which only happens in 65C816 native mode
+    fn is_16_bit_operation(&self, instruction: &Instruction) -> bool {
         match instruction.mnemonic {
-            Mnemonic::NOP 
-                => evaluate_nop(self),
-            Mnemonic::LDA | Mnemonic::LDX | Mnemonic::LDY 
-                => evaluate_load(self, instruction.mnemonic, instruction.addressing_mode),
-            Mnemonic::STA | Mnemonic::STX | Mnemonic::STY 
-                => evaluate_store(self, instruction.mnemonic, instruction.addressing_mode),
-            Mnemonic::TAX | Mnemonic::TAY | Mnemonic::TSX | Mnemonic::TXA | Mnemonic::TXS | Mnemonic::TYA 
-                => evaluate_transfer(self, instruction.mnemonic, instruction.addressing_mode) 
+            Mnemonic::LDA | Mnemonic::STA | Mnemonic::STZ | Mnemonic::ADC | Mnemonic::SBC | Mnemonic::CMP
+            | Mnemonic::AND | Mnemonic::ORA | Mnemonic::EOR | Mnemonic::BIT | Mnemonic::TRB | Mnemonic::TSB
+            | Mnemonic::ASL | Mnemonic::LSR | Mnemonic::ROL | Mnemonic::ROR | Mnemonic::INC | Mnemonic::DEC
+            | Mnemonic::PHA | Mnemonic::PLA | Mnemonic::TXA | Mnemonic::TYA
+                => self.state.accumulator_16(),
+            Mnemonic::LDX | Mnemonic::LDY | Mnemonic::STX | Mnemonic::STY | Mnemonic::CPX | Mnemonic::CPY
+            | Mnemonic::INX | Mnemonic::INY | Mnemonic::DEX | Mnemonic::DEY
+            | Mnemonic::PHX | Mnemonic::PHY | Mnemonic::PLX | Mnemonic::PLY
+            | Mnemonic::TAX | Mnemonic::TAY | Mnemonic::TSX | Mnemonic::TXY | Mnemonic::TYX
+                => self.state.index_16(),
+            // S is 16 bits wide in native mode even with 8-bit index registers
+            Mnemonic::TXS => !self.state.e,
+            _ => false,
+        }
+    }
+
+    /// runs the evaluator of a decoded opcode and returns the cycles it took
+    fn execute_instruction(&mut self, decoded: &DecodedOpcode, wide: bool) -> Result<u8, CpuError> {
+        let Instruction { opcode, mnemonic, addressing_mode, .. } = decoded.instruction;
+        if wide {
+            return evaluate_wide(self, mnemonic, addressing_mode);
+        }
+
+        match decoded.handler {
+            Handler::Nop => evaluate_nop(self, opcode, addressing_mode),
+            Handler::Load => evaluate_load(self, mnemonic, addressing_mode),
+            Handler::Store => evaluate_store(self, mnemonic, addressing_mode),
+            Handler::Transfer => evaluate_transfer(self, mnemonic, addressing_mode),
+            Handler::Arithmetic => evaluate_arithmetic(self, mnemonic, addressing_mode),
+            Handler::Compare => evaluate_compare(self, mnemonic, addressing_mode),
+            Handler::Branch => evaluate_branch(self, mnemonic, addressing_mode),
+            Handler::Stack => evaluate_stack(self, mnemonic, addressing_mode),
+            Handler::Jump => evaluate_jump(self, mnemonic, addressing_mode),
+            Handler::Logical => evaluate_logical(self, mnemonic, addressing_mode),
+            Handler::Shift => evaluate_shift(self, mnemonic, addressing_mode),
+            Handler::Increment => evaluate_increment(self, mnemonic, addressing_mode),
+            Handler::Flag => evaluate_flag(self, mnemonic, addressing_mode),
+            Handler::BitManipulation => evaluate_bit_manipulation(self, opcode, mnemonic, addressing_mode),
+            Handler::Halt => evaluate_halt(self, mnemonic, addressing_mode),
+            Handler::BlockMove => evaluate_block_move(self, mnemonic, addressing_mode),
+            Handler::Interrupt => evaluate_interrupt(self, mnemonic, addressing_mode),
+            Handler::Undocumented => evaluate_undocumented(self, mnemonic, addressing_mode),
         }
     }
 
     /// resolves an addressing mode to the effective address.
     /// increments PC appropriately and also updates page_crossed boolean.
     /// writes given value at computed effective address.
-    pub fn resolve_address_and_set_value(&mut self, addressing_mode: AddressingMode, value: u8) {
-        let addr: u16 = self.resolve_address(addressing_mode);
-        self.memory.write_byte(addr, value);
+    pub fn resolve_address_and_set_value(&mut self, addressing_mode: AddressingMode, value: u8) -> Result<(), CpuError> {
+        let addr: u32 = self.resolve_address_for_write(addressing_mode)?;
+        self.write_byte_long(addr, value);
+        Ok(())
     }
 
     /// resolves an addressing mode to the effective address.
     /// increments PC appropriately and also updates page_crossed boolean.
     /// returns value at computed effective address.
-    pub fn resolve_address_and_get_value(&mut self, addressing_mode: AddressingMode) -> u8 {
-        let addr: u16 = self.resolve_address(addressing_mode);
-        let value: u8 = self.read_byte(addr);
-        value
+    pub fn resolve_address_and_get_value(&mut self, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+        let addr: u32 = self.resolve_address_long(addressing_mode)?;
+        let value: u8 = self.read_byte_long(addr);
+        Ok(value)
+    }
+
+    /// read-modify-write: resolves the addressing mode once, reads the value, writes back the
+    /// result of `modify` and returns it.
+    /// increments PC appropriately and also updates page_crossed boolean.
+    /// Accumulator operates on A instead of memory.
+    pub fn resolve_address_and_modify_value<F>(&mut self, addressing_mode: AddressingMode, modify: F) -> Result<u8, CpuError>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        if let AddressingMode::Accumulator = addressing_mode {
+            let result: u8 = modify(self.state.a);
+            self.state.a = result;
+            return Ok(result);
+        }
+
+        let addr: u32 = self.resolve_address_for_write(addressing_mode)?;
+        let value: u8 = self.read_byte_long(addr);
+        self.read_modify_write_dummy_access(addr, value);
+        let result: u8 = modify(value);
+        self.write_byte_long(addr, result);
+        Ok(result)
+    }
+
+    /// 16-bit counterpart of resolve_address_and_set_value for 65C816 native mode.
+    pub fn resolve_address_and_set_word(&mut self, addressing_mode: AddressingMode, value: u16) -> Result<(), CpuError> {
+        let addr: u32 = self.resolve_address_for_write(addressing_mode)?;
+        self.write_word_long(addr, value);
+        Ok(())
+    }
+
+    /// 16-bit counterpart of resolve_address_and_get_value for 65C816 native mode.
+    /// Immediate fetches a two byte operand.
+    pub fn resolve_address_and_get_word(&mut self, addressing_mode: AddressingMode) -> Result<u16, CpuError> {
+        if let AddressingMode::Immediate = addressing_mode {
+            return Ok(self.fetch_word());
+        }
+
+        let addr: u32 = self.resolve_address_long(addressing_mode)?;
+        let value: u16 = self.read_word_long(addr);
+        Ok(value)
+    }
+
+    /// 16-bit counterpart of resolve_address_and_modify_value for 65C816 native mode.
+    /// Accumulator operates on C instead of memory.
+    pub fn resolve_address_and_modify_word<F>(&mut self, addressing_mode: AddressingMode, modify: F) -> Result<u16, CpuError>
+    where
+        F: FnOnce(u16) -> u16,
+    {
+        if let AddressingMode::Accumulator = addressing_mode {
+            let result: u16 = modify(self.state.accumulator());
+            self.state.set_accumulator(result);
+            return Ok(result);
+        }
+
+        let addr: u32 = self.resolve_address_for_write(addressing_mode)?;
+        let value: u16 = self.read_word_long(addr);
+        let result: u16 = modify(value);
+        self.write_word_long(addr, result);
+        Ok(result)
     }
 
     /// resolves an addressing mode to the effective address.
     /// increments PC appropriately and also updates page_crossed boolean.
-    fn resolve_address(&mut self, addressing_mode: AddressingMode) -> u16 {
-            match addressing_mode {
-                AddressingMode::Immediate => self.fetch_operand_address(),
-                AddressingMode::ZeroPage | AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => {
-                    let addr: u8 = self.fetch_byte();
-                    let effective_addr: u8 = match addressing_mode {
-                        AddressingMode::ZeroPageX => addr.wrapping_add(self.get_state().x),
-                        AddressingMode::ZeroPageY => addr.wrapping_add(self.get_state().y),
-                        // cannot match with ZeroPage directly because compiler is stupid and can't infer ZeroPage being the only other possibility.
-                        _ => addr,
-                    };
-                    effective_addr as u16
-                },
-                AddressingMode::Absolute | AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => {
-                    let addr: u16 = self.fetch_word();
-                    let effective_addr: u16 = match addressing_mode {
-                        AddressingMode::AbsoluteX => addr.wrapping_add(self.get_state().x as u16),
-                        AddressingMode::AbsoluteY => addr.wrapping_add(self.get_state().y as u16),
-                        // cannot match with Absolute directly because compiler is stupid and can't infer Absolute being the only other possibility.
+    /// for Relative the effective address is the branch target, page crossing is measured against
+    /// the address of the next instruction.
+    /// only the low 16 bits of 65C816 addresses are returned, see resolve_address_long.
+    pub fn resolve_address(&mut self, addressing_mode: AddressingMode) -> Result<u16, CpuError> {
+        Ok(self.resolve_address_long(addressing_mode)? as u16)
+    }
+
+    /// resolves an addressing mode to the 24-bit effective address.
+    /// data addresses are in the data bank, zeropage and stack addresses in bank 0, and jump and
+    /// branch targets in the program bank. indexing may carry into the next bank on the 65C816,
+    /// every other variant stays in its 16-bit address space.
+    /// Implied, Accumulator, ZeroPageRelative and BlockMove have no single effective address.
+    pub fn resolve_address_long(&mut self, addressing_mode: AddressingMode) -> Result<u32, CpuError> {
+        self.resolve_effective_address(addressing_mode, false)
+    }
+
+    /// resolve_address_long for stores and read-modify-write instructions.
+    /// the NMOS 6502 cannot undo a write to the un-carried address, so indexed writes always
+    /// spend the cycle reading it, not only when indexing crosses a page.
+    pub fn resolve_address_for_write(&mut self, addressing_mode: AddressingMode) -> Result<u32, CpuError> {
+        self.resolve_effective_address(addressing_mode, true)
+    }
+
+    fn resolve_effective_address(&mut self, addressing_mode: AddressingMode, write: bool) -> Result<u32, CpuError> {
+        let always_fix_carry: bool = write && !self.variant.is_cmos();
+        let x: u16 = self.get_state().index_x();
+        let y: u16 = self.get_state().index_y();
+
+        let effective_addr: u32 = match addressing_mode {
+            AddressingMode::Immediate => {
+                let addr: u16 = self.fetch_operand_address();
+                self.program_address(addr)
+            },
+            AddressingMode::ZeroPage | AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => {
+                let addr: u16 = self.fetch_byte() as u16;
+                // the NMOS 6502 reads the unindexed address while adding the index
+                if addressing_mode != AddressingMode::ZeroPage && !self.variant.is_cmos() {
+                    self.dummy_read(self.direct_address(addr));
+                }
+                let offset: u16 = match addressing_mode {
+                    AddressingMode::ZeroPageX => addr.wrapping_add(x),
+                    AddressingMode::ZeroPageY => addr.wrapping_add(y),
+                    // cannot match with ZeroPage directly because compiler is stupid and can't infer ZeroPage being the only other possibility.
+                    _ => addr,
+                };
+                self.direct_address(offset)
+            },
+            AddressingMode::Absolute | AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => {
+                let addr: u16 = self.fetch_word();
+                let index: u16 = match addressing_mode {
+                    AddressingMode::AbsoluteX => x,
+                    AddressingMode::AbsoluteY => y,
+                    // cannot match with Absolute directly because compiler is stupid and can't infer Absolute being the only other possibility.
+                    _ => 0,
+                };
+                self.update_page_cross(addr, addr.wrapping_add(index));
+                if self.page_crossed || (always_fix_carry && addressing_mode != AddressingMode::Absolute) {
+                    self.dummy_read_uncarried(addr, index);
+                }
+                self.data_address(addr) + index as u32
+            },
+            AddressingMode::AbsoluteLong | AddressingMode::AbsoluteLongX => {
+                let addr: u32 = self.fetch_long();
+                match addressing_mode {
+                    AddressingMode::AbsoluteLongX => addr + x as u32,
+                    _ => addr,
+                }
+            },
+            AddressingMode::IndirectX => {
+                    let indirect_addr: u16 = self.fetch_byte() as u16;
+                    if !self.variant.is_cmos() {
+                        self.dummy_read(self.direct_address(indirect_addr));
+                    }
+                    let effective_addr: u16 = self.read_direct_word(indirect_addr.wrapping_add(x));
+                    self.data_address(effective_addr)
+            },
+            AddressingMode::IndirectY => {
+                    let indirect_addr: u16 = self.fetch_byte() as u16;
+                    let effective_addr_unindexed: u16 = self.read_direct_word(indirect_addr);
+                    self.update_page_cross(effective_addr_unindexed, effective_addr_unindexed.wrapping_add(y));
+                    if self.page_crossed || always_fix_carry {
+                        self.dummy_read_uncarried(effective_addr_unindexed, y);
+                    }
+                    self.data_address(effective_addr_unindexed) + y as u32
+            },
+            AddressingMode::ZeroPageIndirect => {
+                    let indirect_addr: u16 = self.fetch_byte() as u16;
+                    let effective_addr: u16 = self.read_direct_word(indirect_addr);
+                    self.data_address(effective_addr)
+            },
+            AddressingMode::ZeroPageIndirectLong | AddressingMode::ZeroPageIndirectLongY => {
+                    let indirect_addr: u16 = self.fetch_byte() as u16;
+                    let lo: u16 = self.read_direct_word(indirect_addr);
+                    let bank: u8 = self.read_byte_long(self.direct_address(indirect_addr.wrapping_add(2)));
+                    let addr: u32 = ((bank as u32) << 16) | lo as u32;
+                    match addressing_mode {
+                        AddressingMode::ZeroPageIndirectLongY => addr + y as u32,
                         _ => addr,
+                    }
+            },
+            AddressingMode::StackRelative => {
+                    let offset: u16 = self.fetch_byte() as u16;
+                    self.get_state().stack_pointer().wrapping_add(offset) as u32
+            },
+            AddressingMode::StackRelativeIndirectY => {
+                    let offset: u16 = self.fetch_byte() as u16;
+                    let indirect_addr: u16 = self.get_state().stack_pointer().wrapping_add(offset);
+                    let effective_addr_unindexed: u16 = self.read_word(indirect_addr);
+                    self.data_address(effective_addr_unindexed) + y as u32
+            },
+            AddressingMode::Relative => {
+                    let offset: i8 = self.fetch_byte() as i8;
+                    let next_instruction_addr: u16 = self.state.pc;
+                    let effective_addr: u16 = next_instruction_addr.wrapping_add(offset as u16);
+                    self.update_page_cross(next_instruction_addr, effective_addr);
+                    self.program_address(effective_addr)
+            },
+            AddressingMode::RelativeLong => {
+                    let offset: u16 = self.fetch_word();
+                    let effective_addr: u16 = self.state.pc.wrapping_add(offset);
+                    self.program_address(effective_addr)
+            },
+            AddressingMode::Indirect => {
+                    let indirect_addr: u16 = self.fetch_word();
+                    // the 65C02 fixed the NMOS page wrap bug
+                    let effective_addr: u16 = if self.variant.is_cmos() {
+                        self.read_word(indirect_addr)
+                    } else {
+                        self.read_word_page_wrapped(indirect_addr)
                     };
-                    self.update_page_cross(addr, effective_addr);
-                    effective_addr
-                },
-                AddressingMode::IndirectX => {
-                        let indirect_addr: u8 = self.fetch_byte();
-                        let indirect_addr_indexed: u8 = indirect_addr.wrapping_add(self.get_state().x);
-                        let effective_addr: u16 = self.read_word(indirect_addr_indexed as u16);
-                        effective_addr
-                },
-                AddressingMode::IndirectY => {
-                        let indirect_addr: u8 = self.fetch_byte();
-                        let effective_addr_unindexed: u16 = self.read_word(indirect_addr as u16);
-                        let effective_addr: u16 = effective_addr_unindexed.wrapping_add(self.get_state().y as u16);
-                        self.update_page_cross(effective_addr_unindexed, effective_addr);
-                        effective_addr
-                },
-                _ => panic!("unimplemented AddressingMode handling for: {:?}", self),
-            }
+                    self.program_address(effective_addr)
+            },
+            AddressingMode::AbsoluteIndirectX => {
+                    let indirect_addr: u16 = self.fetch_word();
+                    let indirect_addr_indexed: u16 = indirect_addr.wrapping_add(x);
+                    let effective_addr: u16 = self.read_word_long(self.program_address(indirect_addr_indexed));
+                    self.program_address(effective_addr)
+            },
+            AddressingMode::AbsoluteIndirectLong => {
+                    let indirect_addr: u16 = self.fetch_word();
+                    let lo: u16 = self.read_word(indirect_addr);
+                    let bank: u8 = self.read_byte(indirect_addr.wrapping_add(2));
+                    ((bank as u32) << 16) | lo as u32
+            },
+            AddressingMode::Implied | AddressingMode::Accumulator | AddressingMode::ZeroPageRelative | AddressingMode::BlockMove
+                => return Err(CpuError::UnsupportedAddressingMode(addressing_mode)),
+        };
+
+        if self.variant.is_65816() {
+            Ok(effective_addr & 0xFF_FFFF)
+        } else {
+            Ok(effective_addr & 0xFFFF)
+        }
     }
-}
\ No newline at end of file
+}
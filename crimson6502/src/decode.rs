@@ -0,0 +1,145 @@
+use crate::instruction::{AddressingMode, Instruction, Mnemonic};
+use crate::instruction_evaluation::{cycles_branch, cycles_branch_on_bit, cycles_flag, cycles_increment, cycles_interrupt, cycles_jump, cycles_logical, cycles_nop, cycles_read_modify_write, cycles_register_transfer, cycles_shift, cycles_stack, cycles_transfer, cycles_undocumented, CYCLES_BLOCK_MOVE, CYCLES_HALT};
+use crate::variant::CPUVariant;
+
+/// the evaluator an opcode is dispatched to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Handler {
+    Nop,
+    Load,
+    Store,
+    Transfer,
+    Arithmetic,
+    Compare,
+    Branch,
+    Stack,
+    Jump,
+    Logical,
+    Shift,
+    Increment,
+    Flag,
+    BitManipulation,
+    Halt,
+    BlockMove,
+    Interrupt,
+    Undocumented,
+}
+
+impl Handler {
+    const fn for_mnemonic(mnemonic: Mnemonic) -> Handler {
+        match mnemonic {
+            Mnemonic::NOP | Mnemonic::WDM => Handler::Nop,
+            Mnemonic::LDA | Mnemonic::LDX | Mnemonic::LDY => Handler::Load,
+            Mnemonic::STA | Mnemonic::STX | Mnemonic::STY | Mnemonic::STZ => Handler::Store,
+            Mnemonic::TAX | Mnemonic::TAY | Mnemonic::TSX | Mnemonic::TXA | Mnemonic::TXS | Mnemonic::TYA
+            | Mnemonic::TCS | Mnemonic::TSC | Mnemonic::TCD | Mnemonic::TDC | Mnemonic::TXY | Mnemonic::TYX | Mnemonic::XBA
+                => Handler::Transfer,
+            Mnemonic::ADC | Mnemonic::SBC => Handler::Arithmetic,
+            Mnemonic::CMP | Mnemonic::CPX | Mnemonic::CPY => Handler::Compare,
+            Mnemonic::BCC | Mnemonic::BCS | Mnemonic::BEQ | Mnemonic::BMI | Mnemonic::BNE | Mnemonic::BPL | Mnemonic::BVC | Mnemonic::BVS | Mnemonic::BRA | Mnemonic::BRL
+                => Handler::Branch,
+            Mnemonic::PHA | Mnemonic::PHP | Mnemonic::PLA | Mnemonic::PLP | Mnemonic::PHX | Mnemonic::PHY | Mnemonic::PLX | Mnemonic::PLY
+            | Mnemonic::PHD | Mnemonic::PLD | Mnemonic::PHK | Mnemonic::PHB | Mnemonic::PLB | Mnemonic::PEA | Mnemonic::PEI | Mnemonic::PER
+                => Handler::Stack,
+            Mnemonic::JMP | Mnemonic::JSR | Mnemonic::RTS | Mnemonic::JML | Mnemonic::JSL | Mnemonic::RTL => Handler::Jump,
+            Mnemonic::AND | Mnemonic::ORA | Mnemonic::EOR | Mnemonic::BIT | Mnemonic::TRB | Mnemonic::TSB => Handler::Logical,
+            Mnemonic::ASL | Mnemonic::LSR | Mnemonic::ROL | Mnemonic::ROR => Handler::Shift,
+            Mnemonic::INC | Mnemonic::DEC | Mnemonic::INX | Mnemonic::INY | Mnemonic::DEX | Mnemonic::DEY => Handler::Increment,
+            Mnemonic::CLC | Mnemonic::SEC | Mnemonic::CLI | Mnemonic::SEI | Mnemonic::CLD | Mnemonic::SED | Mnemonic::CLV
+            | Mnemonic::REP | Mnemonic::SEP | Mnemonic::XCE
+                => Handler::Flag,
+            Mnemonic::RMB | Mnemonic::SMB | Mnemonic::BBR | Mnemonic::BBS => Handler::BitManipulation,
+            Mnemonic::WAI | Mnemonic::STP => Handler::Halt,
+            Mnemonic::MVN | Mnemonic::MVP => Handler::BlockMove,
+            Mnemonic::BRK | Mnemonic::RTI | Mnemonic::COP => Handler::Interrupt,
+            Mnemonic::SLO | Mnemonic::RLA | Mnemonic::SRE | Mnemonic::RRA | Mnemonic::SAX | Mnemonic::LAX | Mnemonic::DCP | Mnemonic::ISC
+            | Mnemonic::ANC | Mnemonic::ALR | Mnemonic::ARR | Mnemonic::SBX | Mnemonic::ANE | Mnemonic::LXA | Mnemonic::LAS | Mnemonic::TAS
+            | Mnemonic::SHA | Mnemonic::SHX | Mnemonic::SHY | Mnemonic::JAM
+                => Handler::Undocumented,
+        }
+    }
+}
+
+/// an opcode decoded ahead of time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecodedOpcode {
+    pub instruction: Instruction,
+    /// operand bytes after the opcode, 16-bit immediates take one more
+    pub length: u8,
+    /// cycles in emulation mode without page crossings, taken branches or decimal and direct page penalties
+    pub base_cycles: u8,
+    pub(crate) handler: Handler,
+}
+
+static NMOS_TABLE: [Option<DecodedOpcode>; 256] = decode_table(CPUVariant::Nmos6502);
+static CMOS_TABLE: [Option<DecodedOpcode>; 256] = decode_table(CPUVariant::Cmos65C02);
+static WDC_TABLE: [Option<DecodedOpcode>; 256] = decode_table(CPUVariant::Wdc65C02);
+static WDC_65816_TABLE: [Option<DecodedOpcode>; 256] = decode_table(CPUVariant::Wdc65C816);
+
+impl DecodedOpcode {
+    /// looks up an opcode in the decode table of the variant
+    pub fn from_byte(byte: u8, variant: CPUVariant) -> Option<&'static DecodedOpcode> {
+        let table: &'static [Option<DecodedOpcode>; 256] = match variant {
+            CPUVariant::Nmos6502 | CPUVariant::Ricoh2A03 => &NMOS_TABLE,
+            CPUVariant::Cmos65C02 => &CMOS_TABLE,
+            CPUVariant::Wdc65C02 => &WDC_TABLE,
+            CPUVariant::Wdc65C816 => &WDC_65816_TABLE,
+        };
+        table[byte as usize].as_ref()
+    }
+
+    const fn decode(byte: u8, variant: CPUVariant) -> Option<DecodedOpcode> {
+        let Some(instruction) = Instruction::decode(byte, variant) else {
+            return None;
+        };
+        let handler: Handler = Handler::for_mnemonic(instruction.mnemonic);
+        Some(Self {
+            instruction,
+            length: instruction.addressing_mode.instruction_length(),
+            base_cycles: base_cycles(handler, instruction, variant),
+            handler,
+        })
+    }
+}
+
+const fn decode_table(variant: CPUVariant) -> [Option<DecodedOpcode>; 256] {
+    let mut table: [Option<DecodedOpcode>; 256] = [None; 256];
+    let mut byte: usize = 0;
+    while byte < table.len() {
+        table[byte] = DecodedOpcode::decode(byte as u8, variant);
+        byte += 1;
+    }
+    table
+}
+
+/// the cycles the evaluators report for the cheapest execution of the instruction
+const fn base_cycles(handler: Handler, instruction: Instruction, variant: CPUVariant) -> u8 {
+    let mnemonic: Mnemonic = instruction.mnemonic;
+    let addressing_mode: AddressingMode = instruction.addressing_mode;
+    let cycles = match handler {
+        Handler::Nop => cycles_nop(instruction.opcode, addressing_mode, variant, false),
+        Handler::Load | Handler::Arithmetic | Handler::Compare => cycles_transfer(addressing_mode, false),
+        Handler::Store => cycles_transfer(addressing_mode, true),
+        Handler::Transfer => Ok(cycles_register_transfer(mnemonic)),
+        Handler::Branch => Ok(cycles_branch(addressing_mode, false, false)),
+        Handler::Stack => cycles_stack(mnemonic),
+        Handler::Jump => cycles_jump(mnemonic, addressing_mode, variant),
+        Handler::Logical => cycles_logical(mnemonic, addressing_mode, false),
+        Handler::Shift => cycles_shift(addressing_mode, variant, false),
+        Handler::Increment => cycles_increment(mnemonic, addressing_mode),
+        Handler::Flag => Ok(cycles_flag(mnemonic)),
+        Handler::BitManipulation => match mnemonic {
+            Mnemonic::RMB | Mnemonic::SMB => cycles_read_modify_write(addressing_mode),
+            _ => Ok(cycles_branch_on_bit(false, false)),
+        },
+        Handler::Halt => Ok(CYCLES_HALT),
+        Handler::BlockMove => Ok(CYCLES_BLOCK_MOVE),
+        Handler::Interrupt => cycles_interrupt(mnemonic, false),
+        Handler::Undocumented => cycles_undocumented(mnemonic, addressing_mode, false),
+    };
+    match cycles {
+        Ok(cycles) => cycles,
+        // the evaluator rejects the instruction, it never completes
+        Err(_) => 0,
+    }
+}
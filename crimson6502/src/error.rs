@@ -0,0 +1,36 @@
+use std::fmt;
+use crate::cpu::RunState;
+use crate::instruction::{AddressingMode, Mnemonic};
+
+/// why the CPU could not execute the next instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuError {
+    /// the byte at addr does not decode to an instruction on this variant, or is an undocumented
+    /// opcode while those are disabled
+    IllegalOpcode { opcode: u8, addr: u32 },
+    /// an evaluator received a mnemonic it does not implement
+    UnsupportedMnemonic(Mnemonic),
+    /// an address or cycle count was requested for an addressing mode that has none
+    UnsupportedAddressingMode(AddressingMode),
+    /// JAM or STP halted the CPU at addr, only a reset resumes it
+    Halted { run_state: RunState, addr: u32 },
+    /// the bus rejected an access to addr
+    BusFault { addr: u32 },
+    /// execution reached a breakpoint, the instruction at addr has not run yet
+    Breakpoint { addr: u32 },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuError::IllegalOpcode { opcode, addr } => write!(f, "illegal opcode ${:02X} at ${:06X}", opcode, addr),
+            CpuError::UnsupportedMnemonic(mnemonic) => write!(f, "unsupported mnemonic {:?}", mnemonic),
+            CpuError::UnsupportedAddressingMode(addressing_mode) => write!(f, "unsupported addressing mode {:?}", addressing_mode),
+            CpuError::Halted { run_state, addr } => write!(f, "CPU halted ({:?}) at ${:06X}", run_state, addr),
+            CpuError::BusFault { addr } => write!(f, "bus fault at ${:06X}", addr),
+            CpuError::Breakpoint { addr } => write!(f, "breakpoint at ${:06X}", addr),
+        }
+    }
+}
+
+impl std::error::Error for CpuError {}
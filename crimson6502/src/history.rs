@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+use crate::cpu::ExecutionSnapshot;
+
+/// a byte overwritten by a write, with the bus latches before the write
+#[derive(Debug, PartialEq)]
+pub(crate) struct OverwrittenByte {
+    pub(crate) addr: u32,
+    pub(crate) value: u8,
+    pub(crate) latches: Vec<u32>,
+}
+
+/// an executed instruction or interrupt sequence: the CPU before it and the bytes it overwrote
+#[derive(Debug, PartialEq)]
+pub(crate) struct HistoryEntry {
+    pub(crate) before: ExecutionSnapshot,
+    /// in the order of the writes
+    pub(crate) overwritten: Vec<OverwrittenByte>,
+}
+
+/// bounded record of executed instructions for stepping backwards.
+/// when full, the oldest entry is dropped.
+#[derive(Debug, PartialEq)]
+pub(crate) struct History {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+    /// entry of the instruction being executed
+    current: Option<HistoryEntry>,
+}
+
+impl History {
+    pub(crate) fn new(capacity: usize) -> History {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            current: None,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// opens the entry of the instruction about to execute
+    pub(crate) fn begin(&mut self, before: ExecutionSnapshot) {
+        self.current = Some(HistoryEntry { before, overwritten: Vec::new() });
+    }
+
+    /// records the value a write of the current instruction replaces and the latches it may change
+    pub(crate) fn record_write(&mut self, addr: u32, previous_value: u8, latches: Vec<u32>) {
+        if let Some(entry) = &mut self.current {
+            entry.overwritten.push(OverwrittenByte { addr, value: previous_value, latches });
+        }
+    }
+
+    /// closes the current entry. it is kept if `executed` says the CPU ran anything since the
+    /// snapshot or bytes were overwritten, a breakpoint or waiting CPU leaves nothing to undo.
+    pub(crate) fn end<F>(&mut self, executed: F)
+    where
+        F: FnOnce(&ExecutionSnapshot) -> bool,
+    {
+        let Some(entry) = self.current.take() else {
+            return;
+        };
+        if !executed(&entry.before) && entry.overwritten.is_empty() {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        if self.capacity > 0 {
+            self.entries.push_back(entry);
+        }
+    }
+
+    /// the current entry, e.g. of an instruction abandoned in the middle
+    pub(crate) fn take_current(&mut self) -> Option<HistoryEntry> {
+        self.current.take()
+    }
+
+    /// removes the most recent entry
+    pub(crate) fn pop(&mut self) -> Option<HistoryEntry> {
+        self.entries.pop_back()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.current = None;
+    }
+}
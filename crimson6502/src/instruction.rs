@@ -1,14 +1,41 @@
-#[derive(Debug, Clone, Copy)]
+use crate::decode::DecodedOpcode;
+use crate::variant::CPUVariant;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Mnemonic {
     NOP,
     LDA, LDX, LDY,
     STA, STX, STY,
-    TAX, TAY, TSX, TXA, TXS, TYA
+    TAX, TAY, TSX, TXA, TXS, TYA,
+    ADC, SBC,
+    CMP, CPX, CPY,
+    BCC, BCS, BEQ, BMI, BNE, BPL, BVC, BVS,
+    PHA, PHP, PLA, PLP,
+    JMP, JSR, RTS,
+    BRK, RTI,
+    AND, ORA, EOR, BIT,
+    ASL, LSR, ROL, ROR,
+    INC, DEC, INX, INY, DEX, DEY,
+    CLC, SEC, CLI, SEI, CLD, SED, CLV,
+    // 65C02
+    BRA, PHX, PHY, PLX, PLY, STZ, TRB, TSB,
+    // W65C02S, the bit number of RMB/SMB/BBR/BBS is bits 4-6 of the opcode
+    RMB, SMB, BBR, BBS, WAI, STP,
+    // 65C816
+    BRL, JML, JSL, RTL, COP, WDM, REP, SEP, XCE, XBA,
+    PHB, PHD, PHK, PLB, PLD, PEA, PEI, PER,
+    TCD, TCS, TDC, TSC, TXY, TYX, MVN, MVP,
+    // undocumented NMOS opcodes
+    SLO, RLA, SRE, RRA, SAX, LAX, DCP, ISC,
+    ANC, ALR, ARR, SBX, ANE, LXA, LAS, TAS, SHA, SHX, SHY,
+    JAM,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AddressingMode {
     Implied,
+    Accumulator,
     Immediate,
     ZeroPage,
     ZeroPageX,
@@ -17,35 +44,283 @@ pub enum AddressingMode {
     AbsoluteX,
     AbsoluteY,
     IndirectX,
-    IndirectY
+    IndirectY,
+    Relative,
+    Indirect,
+    /// (zp), 65C02 only
+    ZeroPageIndirect,
+    /// (abs,X), 65C02 JMP only
+    AbsoluteIndirectX,
+    /// zeropage address followed by a branch offset, BBR/BBS only
+    ZeroPageRelative,
+    // 65C816 only. the zeropage modes address the direct page
+    /// long: 24-bit address
+    AbsoluteLong,
+    /// long,X
+    AbsoluteLongX,
+    /// [dp]: 24-bit pointer in the direct page
+    ZeroPageIndirectLong,
+    /// [dp],Y
+    ZeroPageIndirectLongY,
+    /// sr,S: offset from the stack pointer
+    StackRelative,
+    /// (sr,S),Y
+    StackRelativeIndirectY,
+    /// 16-bit branch offset, BRL and PER
+    RelativeLong,
+    /// [abs]: 24-bit pointer in bank 0, JML only
+    AbsoluteIndirectLong,
+    /// destination bank followed by source bank, MVN/MVP only
+    BlockMove,
 }
 
 impl AddressingMode {
-    pub fn instruction_length(&self) -> u8 {
+    pub const fn instruction_length(&self) -> u8 {
         match self {
-            AddressingMode::Implied => 0,
+            AddressingMode::Implied | AddressingMode::Accumulator => 0,
             AddressingMode::Immediate => 1,
             AddressingMode::ZeroPage | AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => 1,
             AddressingMode::Absolute | AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => 2,
-            AddressingMode::IndirectX | AddressingMode::IndirectY => 2,
+            AddressingMode::IndirectX | AddressingMode::IndirectY => 1,
+            AddressingMode::Indirect | AddressingMode::AbsoluteIndirectX => 2,
+            AddressingMode::ZeroPageIndirect => 1,
+            AddressingMode::ZeroPageRelative => 2,
+            AddressingMode::AbsoluteLong | AddressingMode::AbsoluteLongX => 3,
+            AddressingMode::ZeroPageIndirectLong | AddressingMode::ZeroPageIndirectLongY => 1,
+            AddressingMode::StackRelative | AddressingMode::StackRelativeIndirectY => 1,
+            AddressingMode::RelativeLong | AddressingMode::AbsoluteIndirectLong | AddressingMode::BlockMove => 2,
+            AddressingMode::Relative => 1,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Instruction {
+    pub opcode: u8,
     pub mnemonic: Mnemonic, 
     pub addressing_mode: AddressingMode,
+    /// whether the opcode is outside the documented instruction set of the variant
+    pub undocumented: bool,
 }
 
 impl Instruction {
-    pub fn new(mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Self {
+    pub const fn new(mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Self {
+        Self {
+            opcode: 0,
+            mnemonic,
+            addressing_mode,
+            undocumented: false,
+        }
+    }
+
+    pub const fn undocumented(mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Self {
         Self {
+            opcode: 0,
             mnemonic,
             addressing_mode,
+            undocumented: true,
         }
     }
-    pub fn from_byte(byte: u8) -> Option<Self> {
+
+    /// decodes an opcode for the given variant
+    pub fn from_byte(byte: u8, variant: CPUVariant) -> Option<Self> {
+        DecodedOpcode::from_byte(byte, variant).map(|decoded| decoded.instruction)
+    }
+
+    /// builds the decode tables, see `DecodedOpcode`
+    pub(crate) const fn decode(byte: u8, variant: CPUVariant) -> Option<Self> {
+        let instruction: Option<Self> = match variant {
+            CPUVariant::Cmos65C02 => Self::from_byte_cmos(byte),
+            CPUVariant::Wdc65C02 => Self::from_byte_wdc(byte),
+            CPUVariant::Wdc65C816 => Self::from_byte_65816(byte),
+            CPUVariant::Nmos6502 | CPUVariant::Ricoh2A03 => Self::from_byte_nmos(byte),
+        };
+        match instruction {
+            Some(instruction) => Some(Self { opcode: byte, ..instruction }),
+            None => None,
+        }
+    }
+
+    /// 65C816 decoding: every opcode is defined, the columns left undefined by the 65C02 hold
+    /// the new addressing modes and instructions
+    const fn from_byte_65816(byte: u8) -> Option<Self> {
+        match byte {
+            //ORA-----------------------------------------------------------------------------------
+            0x03 => Some(Self::new(Mnemonic::ORA, AddressingMode::StackRelative)),
+            0x13 => Some(Self::new(Mnemonic::ORA, AddressingMode::StackRelativeIndirectY)),
+            0x07 => Some(Self::new(Mnemonic::ORA, AddressingMode::ZeroPageIndirectLong)),
+            0x17 => Some(Self::new(Mnemonic::ORA, AddressingMode::ZeroPageIndirectLongY)),
+            0x0F => Some(Self::new(Mnemonic::ORA, AddressingMode::AbsoluteLong)),
+            0x1F => Some(Self::new(Mnemonic::ORA, AddressingMode::AbsoluteLongX)),
+            //AND-----------------------------------------------------------------------------------
+            0x23 => Some(Self::new(Mnemonic::AND, AddressingMode::StackRelative)),
+            0x33 => Some(Self::new(Mnemonic::AND, AddressingMode::StackRelativeIndirectY)),
+            0x27 => Some(Self::new(Mnemonic::AND, AddressingMode::ZeroPageIndirectLong)),
+            0x37 => Some(Self::new(Mnemonic::AND, AddressingMode::ZeroPageIndirectLongY)),
+            0x2F => Some(Self::new(Mnemonic::AND, AddressingMode::AbsoluteLong)),
+            0x3F => Some(Self::new(Mnemonic::AND, AddressingMode::AbsoluteLongX)),
+            //EOR-----------------------------------------------------------------------------------
+            0x43 => Some(Self::new(Mnemonic::EOR, AddressingMode::StackRelative)),
+            0x53 => Some(Self::new(Mnemonic::EOR, AddressingMode::StackRelativeIndirectY)),
+            0x47 => Some(Self::new(Mnemonic::EOR, AddressingMode::ZeroPageIndirectLong)),
+            0x57 => Some(Self::new(Mnemonic::EOR, AddressingMode::ZeroPageIndirectLongY)),
+            0x4F => Some(Self::new(Mnemonic::EOR, AddressingMode::AbsoluteLong)),
+            0x5F => Some(Self::new(Mnemonic::EOR, AddressingMode::AbsoluteLongX)),
+            //ADC-----------------------------------------------------------------------------------
+            0x63 => Some(Self::new(Mnemonic::ADC, AddressingMode::StackRelative)),
+            0x73 => Some(Self::new(Mnemonic::ADC, AddressingMode::StackRelativeIndirectY)),
+            0x67 => Some(Self::new(Mnemonic::ADC, AddressingMode::ZeroPageIndirectLong)),
+            0x77 => Some(Self::new(Mnemonic::ADC, AddressingMode::ZeroPageIndirectLongY)),
+            0x6F => Some(Self::new(Mnemonic::ADC, AddressingMode::AbsoluteLong)),
+            0x7F => Some(Self::new(Mnemonic::ADC, AddressingMode::AbsoluteLongX)),
+            //STA-----------------------------------------------------------------------------------
+            0x83 => Some(Self::new(Mnemonic::STA, AddressingMode::StackRelative)),
+            0x93 => Some(Self::new(Mnemonic::STA, AddressingMode::StackRelativeIndirectY)),
+            0x87 => Some(Self::new(Mnemonic::STA, AddressingMode::ZeroPageIndirectLong)),
+            0x97 => Some(Self::new(Mnemonic::STA, AddressingMode::ZeroPageIndirectLongY)),
+            0x8F => Some(Self::new(Mnemonic::STA, AddressingMode::AbsoluteLong)),
+            0x9F => Some(Self::new(Mnemonic::STA, AddressingMode::AbsoluteLongX)),
+            //LDA-----------------------------------------------------------------------------------
+            0xA3 => Some(Self::new(Mnemonic::LDA, AddressingMode::StackRelative)),
+            0xB3 => Some(Self::new(Mnemonic::LDA, AddressingMode::StackRelativeIndirectY)),
+            0xA7 => Some(Self::new(Mnemonic::LDA, AddressingMode::ZeroPageIndirectLong)),
+            0xB7 => Some(Self::new(Mnemonic::LDA, AddressingMode::ZeroPageIndirectLongY)),
+            0xAF => Some(Self::new(Mnemonic::LDA, AddressingMode::AbsoluteLong)),
+            0xBF => Some(Self::new(Mnemonic::LDA, AddressingMode::AbsoluteLongX)),
+            //CMP-----------------------------------------------------------------------------------
+            0xC3 => Some(Self::new(Mnemonic::CMP, AddressingMode::StackRelative)),
+            0xD3 => Some(Self::new(Mnemonic::CMP, AddressingMode::StackRelativeIndirectY)),
+            0xC7 => Some(Self::new(Mnemonic::CMP, AddressingMode::ZeroPageIndirectLong)),
+            0xD7 => Some(Self::new(Mnemonic::CMP, AddressingMode::ZeroPageIndirectLongY)),
+            0xCF => Some(Self::new(Mnemonic::CMP, AddressingMode::AbsoluteLong)),
+            0xDF => Some(Self::new(Mnemonic::CMP, AddressingMode::AbsoluteLongX)),
+            //SBC-----------------------------------------------------------------------------------
+            0xE3 => Some(Self::new(Mnemonic::SBC, AddressingMode::StackRelative)),
+            0xF3 => Some(Self::new(Mnemonic::SBC, AddressingMode::StackRelativeIndirectY)),
+            0xE7 => Some(Self::new(Mnemonic::SBC, AddressingMode::ZeroPageIndirectLong)),
+            0xF7 => Some(Self::new(Mnemonic::SBC, AddressingMode::ZeroPageIndirectLongY)),
+            0xEF => Some(Self::new(Mnemonic::SBC, AddressingMode::AbsoluteLong)),
+            0xFF => Some(Self::new(Mnemonic::SBC, AddressingMode::AbsoluteLongX)),
+            //BRL-JML-JSL-RTL-----------------------------------------------------------------------
+            0x82 => Some(Self::new(Mnemonic::BRL, AddressingMode::RelativeLong)),
+            0x5C => Some(Self::new(Mnemonic::JML, AddressingMode::AbsoluteLong)),
+            0xDC => Some(Self::new(Mnemonic::JML, AddressingMode::AbsoluteIndirectLong)),
+            0x22 => Some(Self::new(Mnemonic::JSL, AddressingMode::AbsoluteLong)),
+            0xFC => Some(Self::new(Mnemonic::JSR, AddressingMode::AbsoluteIndirectX)),
+            0x6B => Some(Self::new(Mnemonic::RTL, AddressingMode::Implied)),
+            //COP-WDM-------------------------------------------------------------------------------
+            0x02 => Some(Self::new(Mnemonic::COP, AddressingMode::Immediate)),
+            0x42 => Some(Self::new(Mnemonic::WDM, AddressingMode::Immediate)),
+            //REP-SEP-XCE---------------------------------------------------------------------------
+            0xC2 => Some(Self::new(Mnemonic::REP, AddressingMode::Immediate)),
+            0xE2 => Some(Self::new(Mnemonic::SEP, AddressingMode::Immediate)),
+            0xFB => Some(Self::new(Mnemonic::XCE, AddressingMode::Implied)),
+            //PHB-PHD-PHK-PLB-PLD-------------------------------------------------------------------
+            0x8B => Some(Self::new(Mnemonic::PHB, AddressingMode::Implied)),
+            0x0B => Some(Self::new(Mnemonic::PHD, AddressingMode::Implied)),
+            0x4B => Some(Self::new(Mnemonic::PHK, AddressingMode::Implied)),
+            0xAB => Some(Self::new(Mnemonic::PLB, AddressingMode::Implied)),
+            0x2B => Some(Self::new(Mnemonic::PLD, AddressingMode::Implied)),
+            //PEA-PEI-PER---------------------------------------------------------------------------
+            0xF4 => Some(Self::new(Mnemonic::PEA, AddressingMode::Absolute)),
+            0xD4 => Some(Self::new(Mnemonic::PEI, AddressingMode::ZeroPageIndirect)),
+            0x62 => Some(Self::new(Mnemonic::PER, AddressingMode::RelativeLong)),
+            //TCD-TCS-TDC-TSC-TXY-TYX-XBA-----------------------------------------------------------
+            0x5B => Some(Self::new(Mnemonic::TCD, AddressingMode::Implied)),
+            0x1B => Some(Self::new(Mnemonic::TCS, AddressingMode::Implied)),
+            0x7B => Some(Self::new(Mnemonic::TDC, AddressingMode::Implied)),
+            0x3B => Some(Self::new(Mnemonic::TSC, AddressingMode::Implied)),
+            0x9B => Some(Self::new(Mnemonic::TXY, AddressingMode::Implied)),
+            0xBB => Some(Self::new(Mnemonic::TYX, AddressingMode::Implied)),
+            0xEB => Some(Self::new(Mnemonic::XBA, AddressingMode::Implied)),
+            //MVN-MVP-------------------------------------------------------------------------------
+            0x54 => Some(Self::new(Mnemonic::MVN, AddressingMode::BlockMove)),
+            0x44 => Some(Self::new(Mnemonic::MVP, AddressingMode::BlockMove)),
+            //WAI-STP-------------------------------------------------------------------------------
+            0xCB => Some(Self::new(Mnemonic::WAI, AddressingMode::Implied)),
+            0xDB => Some(Self::new(Mnemonic::STP, AddressingMode::Implied)),
+            //--------------------------------------------------------------------------------------
+            _ => Self::from_byte_cmos(byte),
+        }
+    }
+
+    /// W65C02S decoding: bit manipulation and low-power opcodes on top of the 65C02
+    const fn from_byte_wdc(byte: u8) -> Option<Self> {
+        match byte {
+            //RMB-SMB-------------------------------------------------------------------------------
+            0x07 | 0x17 | 0x27 | 0x37 | 0x47 | 0x57 | 0x67 | 0x77
+                => Some(Self::new(Mnemonic::RMB, AddressingMode::ZeroPage)),
+            0x87 | 0x97 | 0xA7 | 0xB7 | 0xC7 | 0xD7 | 0xE7 | 0xF7
+                => Some(Self::new(Mnemonic::SMB, AddressingMode::ZeroPage)),
+            //BBR-BBS-------------------------------------------------------------------------------
+            0x0F | 0x1F | 0x2F | 0x3F | 0x4F | 0x5F | 0x6F | 0x7F
+                => Some(Self::new(Mnemonic::BBR, AddressingMode::ZeroPageRelative)),
+            0x8F | 0x9F | 0xAF | 0xBF | 0xCF | 0xDF | 0xEF | 0xFF
+                => Some(Self::new(Mnemonic::BBS, AddressingMode::ZeroPageRelative)),
+            //WAI-STP-------------------------------------------------------------------------------
+            0xCB => Some(Self::new(Mnemonic::WAI, AddressingMode::Implied)),
+            0xDB => Some(Self::new(Mnemonic::STP, AddressingMode::Implied)),
+            //--------------------------------------------------------------------------------------
+            _ => Self::from_byte_cmos(byte),
+        }
+    }
+
+    /// 65C02 decoding: new instructions and documented NOPs replace the undocumented NMOS opcodes
+    const fn from_byte_cmos(byte: u8) -> Option<Self> {
+        match byte {
+            //BRA-----------------------------------------------------------------------------------
+            0x80 => Some(Self::new(Mnemonic::BRA, AddressingMode::Relative)),
+            //PHX-PHY-PLX-PLY-----------------------------------------------------------------------
+            0xDA => Some(Self::new(Mnemonic::PHX, AddressingMode::Implied)),
+            0x5A => Some(Self::new(Mnemonic::PHY, AddressingMode::Implied)),
+            0xFA => Some(Self::new(Mnemonic::PLX, AddressingMode::Implied)),
+            0x7A => Some(Self::new(Mnemonic::PLY, AddressingMode::Implied)),
+            //STZ-----------------------------------------------------------------------------------
+            0x64 => Some(Self::new(Mnemonic::STZ, AddressingMode::ZeroPage)),
+            0x74 => Some(Self::new(Mnemonic::STZ, AddressingMode::ZeroPageX)),
+            0x9C => Some(Self::new(Mnemonic::STZ, AddressingMode::Absolute)),
+            0x9E => Some(Self::new(Mnemonic::STZ, AddressingMode::AbsoluteX)),
+            //TRB-TSB-------------------------------------------------------------------------------
+            0x14 => Some(Self::new(Mnemonic::TRB, AddressingMode::ZeroPage)),
+            0x1C => Some(Self::new(Mnemonic::TRB, AddressingMode::Absolute)),
+            0x04 => Some(Self::new(Mnemonic::TSB, AddressingMode::ZeroPage)),
+            0x0C => Some(Self::new(Mnemonic::TSB, AddressingMode::Absolute)),
+            //(zp)----------------------------------------------------------------------------------
+            0x12 => Some(Self::new(Mnemonic::ORA, AddressingMode::ZeroPageIndirect)),
+            0x32 => Some(Self::new(Mnemonic::AND, AddressingMode::ZeroPageIndirect)),
+            0x52 => Some(Self::new(Mnemonic::EOR, AddressingMode::ZeroPageIndirect)),
+            0x72 => Some(Self::new(Mnemonic::ADC, AddressingMode::ZeroPageIndirect)),
+            0x92 => Some(Self::new(Mnemonic::STA, AddressingMode::ZeroPageIndirect)),
+            0xB2 => Some(Self::new(Mnemonic::LDA, AddressingMode::ZeroPageIndirect)),
+            0xD2 => Some(Self::new(Mnemonic::CMP, AddressingMode::ZeroPageIndirect)),
+            0xF2 => Some(Self::new(Mnemonic::SBC, AddressingMode::ZeroPageIndirect)),
+            //BIT-----------------------------------------------------------------------------------
+            0x89 => Some(Self::new(Mnemonic::BIT, AddressingMode::Immediate)),
+            0x34 => Some(Self::new(Mnemonic::BIT, AddressingMode::ZeroPageX)),
+            0x3C => Some(Self::new(Mnemonic::BIT, AddressingMode::AbsoluteX)),
+            //INC-DEC-------------------------------------------------------------------------------
+            0x1A => Some(Self::new(Mnemonic::INC, AddressingMode::Accumulator)),
+            0x3A => Some(Self::new(Mnemonic::DEC, AddressingMode::Accumulator)),
+            //JMP-----------------------------------------------------------------------------------
+            0x7C => Some(Self::new(Mnemonic::JMP, AddressingMode::AbsoluteIndirectX)),
+            //NOP-----------------------------------------------------------------------------------
+            0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2
+                => Some(Self::new(Mnemonic::NOP, AddressingMode::Immediate)),
+            0x44 => Some(Self::new(Mnemonic::NOP, AddressingMode::ZeroPage)),
+            0x54 | 0xD4 | 0xF4
+                => Some(Self::new(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0x5C | 0xDC | 0xFC
+                => Some(Self::new(Mnemonic::NOP, AddressingMode::Absolute)),
+            // columns 3, 7, B and F are single byte NOPs
+            _ if byte & 0x03 == 0x03
+                => Some(Self::new(Mnemonic::NOP, AddressingMode::Implied)),
+            //--------------------------------------------------------------------------------------
+            _ => Self::from_byte_nmos(byte),
+        }
+    }
+
+    /// NMOS decoding, documented and undocumented opcodes
+    const fn from_byte_nmos(byte: u8) -> Option<Self> {
         match byte {
             //LDA-----------------------------------------------------------------------------------
             0xA9 => Some(Self::new(Mnemonic::LDA, AddressingMode::Immediate)),
@@ -91,10 +366,260 @@ impl Instruction {
             0x8A => Some(Self::new(Mnemonic::TXA, AddressingMode::Implied)),
             0x9A => Some(Self::new(Mnemonic::TXS, AddressingMode::Implied)),
             0x98 => Some(Self::new(Mnemonic::TYA, AddressingMode::Implied)),
+            //ADC-----------------------------------------------------------------------------------
+            0x69 => Some(Self::new(Mnemonic::ADC, AddressingMode::Immediate)),
+            0x65 => Some(Self::new(Mnemonic::ADC, AddressingMode::ZeroPage)),
+            0x75 => Some(Self::new(Mnemonic::ADC, AddressingMode::ZeroPageX)),
+            0x6D => Some(Self::new(Mnemonic::ADC, AddressingMode::Absolute)),
+            0x7D => Some(Self::new(Mnemonic::ADC, AddressingMode::AbsoluteX)),
+            0x79 => Some(Self::new(Mnemonic::ADC, AddressingMode::AbsoluteY)),
+            0x61 => Some(Self::new(Mnemonic::ADC, AddressingMode::IndirectX)),
+            0x71 => Some(Self::new(Mnemonic::ADC, AddressingMode::IndirectY)),
+            //SBC-----------------------------------------------------------------------------------
+            0xE9 => Some(Self::new(Mnemonic::SBC, AddressingMode::Immediate)),
+            0xE5 => Some(Self::new(Mnemonic::SBC, AddressingMode::ZeroPage)),
+            0xF5 => Some(Self::new(Mnemonic::SBC, AddressingMode::ZeroPageX)),
+            0xED => Some(Self::new(Mnemonic::SBC, AddressingMode::Absolute)),
+            0xFD => Some(Self::new(Mnemonic::SBC, AddressingMode::AbsoluteX)),
+            0xF9 => Some(Self::new(Mnemonic::SBC, AddressingMode::AbsoluteY)),
+            0xE1 => Some(Self::new(Mnemonic::SBC, AddressingMode::IndirectX)),
+            0xF1 => Some(Self::new(Mnemonic::SBC, AddressingMode::IndirectY)),
+            //CMP-----------------------------------------------------------------------------------
+            0xC9 => Some(Self::new(Mnemonic::CMP, AddressingMode::Immediate)),
+            0xC5 => Some(Self::new(Mnemonic::CMP, AddressingMode::ZeroPage)),
+            0xD5 => Some(Self::new(Mnemonic::CMP, AddressingMode::ZeroPageX)),
+            0xCD => Some(Self::new(Mnemonic::CMP, AddressingMode::Absolute)),
+            0xDD => Some(Self::new(Mnemonic::CMP, AddressingMode::AbsoluteX)),
+            0xD9 => Some(Self::new(Mnemonic::CMP, AddressingMode::AbsoluteY)),
+            0xC1 => Some(Self::new(Mnemonic::CMP, AddressingMode::IndirectX)),
+            0xD1 => Some(Self::new(Mnemonic::CMP, AddressingMode::IndirectY)),
+            //CPX-----------------------------------------------------------------------------------
+            0xE0 => Some(Self::new(Mnemonic::CPX, AddressingMode::Immediate)),
+            0xE4 => Some(Self::new(Mnemonic::CPX, AddressingMode::ZeroPage)),
+            0xEC => Some(Self::new(Mnemonic::CPX, AddressingMode::Absolute)),
+            //CPY-----------------------------------------------------------------------------------
+            0xC0 => Some(Self::new(Mnemonic::CPY, AddressingMode::Immediate)),
+            0xC4 => Some(Self::new(Mnemonic::CPY, AddressingMode::ZeroPage)),
+            0xCC => Some(Self::new(Mnemonic::CPY, AddressingMode::Absolute)),
+            //BCC-BCS-BEQ-BMI-BNE-BPL-BVC-BVS-------------------------------------------------------
+            0x90 => Some(Self::new(Mnemonic::BCC, AddressingMode::Relative)),
+            0xB0 => Some(Self::new(Mnemonic::BCS, AddressingMode::Relative)),
+            0xF0 => Some(Self::new(Mnemonic::BEQ, AddressingMode::Relative)),
+            0x30 => Some(Self::new(Mnemonic::BMI, AddressingMode::Relative)),
+            0xD0 => Some(Self::new(Mnemonic::BNE, AddressingMode::Relative)),
+            0x10 => Some(Self::new(Mnemonic::BPL, AddressingMode::Relative)),
+            0x50 => Some(Self::new(Mnemonic::BVC, AddressingMode::Relative)),
+            0x70 => Some(Self::new(Mnemonic::BVS, AddressingMode::Relative)),
+            //PHA-PHP-PLA-PLP-----------------------------------------------------------------------
+            0x48 => Some(Self::new(Mnemonic::PHA, AddressingMode::Implied)),
+            0x08 => Some(Self::new(Mnemonic::PHP, AddressingMode::Implied)),
+            0x68 => Some(Self::new(Mnemonic::PLA, AddressingMode::Implied)),
+            0x28 => Some(Self::new(Mnemonic::PLP, AddressingMode::Implied)),
+            //JMP-JSR-RTS---------------------------------------------------------------------------
+            0x4C => Some(Self::new(Mnemonic::JMP, AddressingMode::Absolute)),
+            0x6C => Some(Self::new(Mnemonic::JMP, AddressingMode::Indirect)),
+            0x20 => Some(Self::new(Mnemonic::JSR, AddressingMode::Absolute)),
+            0x60 => Some(Self::new(Mnemonic::RTS, AddressingMode::Implied)),
+            //BRK-RTI-------------------------------------------------------------------------------
+            0x00 => Some(Self::new(Mnemonic::BRK, AddressingMode::Implied)),
+            0x40 => Some(Self::new(Mnemonic::RTI, AddressingMode::Implied)),
+            //AND-----------------------------------------------------------------------------------
+            0x29 => Some(Self::new(Mnemonic::AND, AddressingMode::Immediate)),
+            0x25 => Some(Self::new(Mnemonic::AND, AddressingMode::ZeroPage)),
+            0x35 => Some(Self::new(Mnemonic::AND, AddressingMode::ZeroPageX)),
+            0x2D => Some(Self::new(Mnemonic::AND, AddressingMode::Absolute)),
+            0x3D => Some(Self::new(Mnemonic::AND, AddressingMode::AbsoluteX)),
+            0x39 => Some(Self::new(Mnemonic::AND, AddressingMode::AbsoluteY)),
+            0x21 => Some(Self::new(Mnemonic::AND, AddressingMode::IndirectX)),
+            0x31 => Some(Self::new(Mnemonic::AND, AddressingMode::IndirectY)),
+            //ORA-----------------------------------------------------------------------------------
+            0x09 => Some(Self::new(Mnemonic::ORA, AddressingMode::Immediate)),
+            0x05 => Some(Self::new(Mnemonic::ORA, AddressingMode::ZeroPage)),
+            0x15 => Some(Self::new(Mnemonic::ORA, AddressingMode::ZeroPageX)),
+            0x0D => Some(Self::new(Mnemonic::ORA, AddressingMode::Absolute)),
+            0x1D => Some(Self::new(Mnemonic::ORA, AddressingMode::AbsoluteX)),
+            0x19 => Some(Self::new(Mnemonic::ORA, AddressingMode::AbsoluteY)),
+            0x01 => Some(Self::new(Mnemonic::ORA, AddressingMode::IndirectX)),
+            0x11 => Some(Self::new(Mnemonic::ORA, AddressingMode::IndirectY)),
+            //EOR-----------------------------------------------------------------------------------
+            0x49 => Some(Self::new(Mnemonic::EOR, AddressingMode::Immediate)),
+            0x45 => Some(Self::new(Mnemonic::EOR, AddressingMode::ZeroPage)),
+            0x55 => Some(Self::new(Mnemonic::EOR, AddressingMode::ZeroPageX)),
+            0x4D => Some(Self::new(Mnemonic::EOR, AddressingMode::Absolute)),
+            0x5D => Some(Self::new(Mnemonic::EOR, AddressingMode::AbsoluteX)),
+            0x59 => Some(Self::new(Mnemonic::EOR, AddressingMode::AbsoluteY)),
+            0x41 => Some(Self::new(Mnemonic::EOR, AddressingMode::IndirectX)),
+            0x51 => Some(Self::new(Mnemonic::EOR, AddressingMode::IndirectY)),
+            //BIT-----------------------------------------------------------------------------------
+            0x24 => Some(Self::new(Mnemonic::BIT, AddressingMode::ZeroPage)),
+            0x2C => Some(Self::new(Mnemonic::BIT, AddressingMode::Absolute)),
+            //ASL-----------------------------------------------------------------------------------
+            0x0A => Some(Self::new(Mnemonic::ASL, AddressingMode::Accumulator)),
+            0x06 => Some(Self::new(Mnemonic::ASL, AddressingMode::ZeroPage)),
+            0x16 => Some(Self::new(Mnemonic::ASL, AddressingMode::ZeroPageX)),
+            0x0E => Some(Self::new(Mnemonic::ASL, AddressingMode::Absolute)),
+            0x1E => Some(Self::new(Mnemonic::ASL, AddressingMode::AbsoluteX)),
+            //LSR-----------------------------------------------------------------------------------
+            0x4A => Some(Self::new(Mnemonic::LSR, AddressingMode::Accumulator)),
+            0x46 => Some(Self::new(Mnemonic::LSR, AddressingMode::ZeroPage)),
+            0x56 => Some(Self::new(Mnemonic::LSR, AddressingMode::ZeroPageX)),
+            0x4E => Some(Self::new(Mnemonic::LSR, AddressingMode::Absolute)),
+            0x5E => Some(Self::new(Mnemonic::LSR, AddressingMode::AbsoluteX)),
+            //ROL-----------------------------------------------------------------------------------
+            0x2A => Some(Self::new(Mnemonic::ROL, AddressingMode::Accumulator)),
+            0x26 => Some(Self::new(Mnemonic::ROL, AddressingMode::ZeroPage)),
+            0x36 => Some(Self::new(Mnemonic::ROL, AddressingMode::ZeroPageX)),
+            0x2E => Some(Self::new(Mnemonic::ROL, AddressingMode::Absolute)),
+            0x3E => Some(Self::new(Mnemonic::ROL, AddressingMode::AbsoluteX)),
+            //ROR-----------------------------------------------------------------------------------
+            0x6A => Some(Self::new(Mnemonic::ROR, AddressingMode::Accumulator)),
+            0x66 => Some(Self::new(Mnemonic::ROR, AddressingMode::ZeroPage)),
+            0x76 => Some(Self::new(Mnemonic::ROR, AddressingMode::ZeroPageX)),
+            0x6E => Some(Self::new(Mnemonic::ROR, AddressingMode::Absolute)),
+            0x7E => Some(Self::new(Mnemonic::ROR, AddressingMode::AbsoluteX)),
+            //INC-----------------------------------------------------------------------------------
+            0xE6 => Some(Self::new(Mnemonic::INC, AddressingMode::ZeroPage)),
+            0xF6 => Some(Self::new(Mnemonic::INC, AddressingMode::ZeroPageX)),
+            0xEE => Some(Self::new(Mnemonic::INC, AddressingMode::Absolute)),
+            0xFE => Some(Self::new(Mnemonic::INC, AddressingMode::AbsoluteX)),
+            //DEC-----------------------------------------------------------------------------------
+            0xC6 => Some(Self::new(Mnemonic::DEC, AddressingMode::ZeroPage)),
+            0xD6 => Some(Self::new(Mnemonic::DEC, AddressingMode::ZeroPageX)),
+            0xCE => Some(Self::new(Mnemonic::DEC, AddressingMode::Absolute)),
+            0xDE => Some(Self::new(Mnemonic::DEC, AddressingMode::AbsoluteX)),
+            //INX-INY-DEX-DEY-----------------------------------------------------------------------
+            0xE8 => Some(Self::new(Mnemonic::INX, AddressingMode::Implied)),
+            0xC8 => Some(Self::new(Mnemonic::INY, AddressingMode::Implied)),
+            0xCA => Some(Self::new(Mnemonic::DEX, AddressingMode::Implied)),
+            0x88 => Some(Self::new(Mnemonic::DEY, AddressingMode::Implied)),
+            //CLC-SEC-CLI-SEI-CLD-SED-CLV-----------------------------------------------------------
+            0x18 => Some(Self::new(Mnemonic::CLC, AddressingMode::Implied)),
+            0x38 => Some(Self::new(Mnemonic::SEC, AddressingMode::Implied)),
+            0x58 => Some(Self::new(Mnemonic::CLI, AddressingMode::Implied)),
+            0x78 => Some(Self::new(Mnemonic::SEI, AddressingMode::Implied)),
+            0xD8 => Some(Self::new(Mnemonic::CLD, AddressingMode::Implied)),
+            0xF8 => Some(Self::new(Mnemonic::SED, AddressingMode::Implied)),
+            0xB8 => Some(Self::new(Mnemonic::CLV, AddressingMode::Implied)),
             //NOP-----------------------------------------------------------------------------------
             0xEA => Some(Self::new(Mnemonic::NOP, AddressingMode::Implied)),
-            //--------------------------------------------------------------------------------------
-            _ => None,
+            //undocumented--------------------------------------------------------------------------
+            //SLO-----------------------------------------------------------------------------------
+            0x07 => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::ZeroPage)),
+            0x17 => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::ZeroPageX)),
+            0x0F => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::Absolute)),
+            0x1F => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::AbsoluteX)),
+            0x1B => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::AbsoluteY)),
+            0x03 => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::IndirectX)),
+            0x13 => Some(Self::undocumented(Mnemonic::SLO, AddressingMode::IndirectY)),
+            //RLA-----------------------------------------------------------------------------------
+            0x27 => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::ZeroPage)),
+            0x37 => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::ZeroPageX)),
+            0x2F => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::Absolute)),
+            0x3F => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::AbsoluteX)),
+            0x3B => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::AbsoluteY)),
+            0x23 => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::IndirectX)),
+            0x33 => Some(Self::undocumented(Mnemonic::RLA, AddressingMode::IndirectY)),
+            //SRE-----------------------------------------------------------------------------------
+            0x47 => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::ZeroPage)),
+            0x57 => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::ZeroPageX)),
+            0x4F => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::Absolute)),
+            0x5F => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::AbsoluteX)),
+            0x5B => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::AbsoluteY)),
+            0x43 => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::IndirectX)),
+            0x53 => Some(Self::undocumented(Mnemonic::SRE, AddressingMode::IndirectY)),
+            //RRA-----------------------------------------------------------------------------------
+            0x67 => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::ZeroPage)),
+            0x77 => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::ZeroPageX)),
+            0x6F => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::Absolute)),
+            0x7F => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::AbsoluteX)),
+            0x7B => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::AbsoluteY)),
+            0x63 => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::IndirectX)),
+            0x73 => Some(Self::undocumented(Mnemonic::RRA, AddressingMode::IndirectY)),
+            //SAX-----------------------------------------------------------------------------------
+            0x87 => Some(Self::undocumented(Mnemonic::SAX, AddressingMode::ZeroPage)),
+            0x97 => Some(Self::undocumented(Mnemonic::SAX, AddressingMode::ZeroPageY)),
+            0x8F => Some(Self::undocumented(Mnemonic::SAX, AddressingMode::Absolute)),
+            0x83 => Some(Self::undocumented(Mnemonic::SAX, AddressingMode::IndirectX)),
+            //LAX-----------------------------------------------------------------------------------
+            0xA7 => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::ZeroPage)),
+            0xB7 => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::ZeroPageY)),
+            0xAF => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::Absolute)),
+            0xBF => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::AbsoluteY)),
+            0xA3 => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::IndirectX)),
+            0xB3 => Some(Self::undocumented(Mnemonic::LAX, AddressingMode::IndirectY)),
+            //DCP-----------------------------------------------------------------------------------
+            0xC7 => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::ZeroPage)),
+            0xD7 => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::ZeroPageX)),
+            0xCF => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::Absolute)),
+            0xDF => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::AbsoluteX)),
+            0xDB => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::AbsoluteY)),
+            0xC3 => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::IndirectX)),
+            0xD3 => Some(Self::undocumented(Mnemonic::DCP, AddressingMode::IndirectY)),
+            //ISC-----------------------------------------------------------------------------------
+            0xE7 => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::ZeroPage)),
+            0xF7 => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::ZeroPageX)),
+            0xEF => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::Absolute)),
+            0xFF => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::AbsoluteX)),
+            0xFB => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::AbsoluteY)),
+            0xE3 => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::IndirectX)),
+            0xF3 => Some(Self::undocumented(Mnemonic::ISC, AddressingMode::IndirectY)),
+            //ANC-ALR-ARR-SBX-SBC-------------------------------------------------------------------
+            0x0B => Some(Self::undocumented(Mnemonic::ANC, AddressingMode::Immediate)),
+            0x2B => Some(Self::undocumented(Mnemonic::ANC, AddressingMode::Immediate)),
+            0x4B => Some(Self::undocumented(Mnemonic::ALR, AddressingMode::Immediate)),
+            0x6B => Some(Self::undocumented(Mnemonic::ARR, AddressingMode::Immediate)),
+            0xCB => Some(Self::undocumented(Mnemonic::SBX, AddressingMode::Immediate)),
+            0xEB => Some(Self::undocumented(Mnemonic::SBC, AddressingMode::Immediate)),
+            //ANE-LXA-LAS-TAS-SHA-SHX-SHY (unstable)------------------------------------------------
+            0x8B => Some(Self::undocumented(Mnemonic::ANE, AddressingMode::Immediate)),
+            0xAB => Some(Self::undocumented(Mnemonic::LXA, AddressingMode::Immediate)),
+            0xBB => Some(Self::undocumented(Mnemonic::LAS, AddressingMode::AbsoluteY)),
+            0x9B => Some(Self::undocumented(Mnemonic::TAS, AddressingMode::AbsoluteY)),
+            0x9F => Some(Self::undocumented(Mnemonic::SHA, AddressingMode::AbsoluteY)),
+            0x93 => Some(Self::undocumented(Mnemonic::SHA, AddressingMode::IndirectY)),
+            0x9E => Some(Self::undocumented(Mnemonic::SHX, AddressingMode::AbsoluteY)),
+            0x9C => Some(Self::undocumented(Mnemonic::SHY, AddressingMode::AbsoluteX)),
+            //NOP-----------------------------------------------------------------------------------
+            0x1A => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0x3A => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0x5A => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0x7A => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0xDA => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0xFA => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Implied)),
+            0x80 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Immediate)),
+            0x82 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Immediate)),
+            0x89 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Immediate)),
+            0xC2 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Immediate)),
+            0xE2 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Immediate)),
+            0x04 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPage)),
+            0x44 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPage)),
+            0x64 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPage)),
+            0x14 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0x34 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0x54 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0x74 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0xD4 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0xF4 => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::ZeroPageX)),
+            0x0C => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::Absolute)),
+            0x1C => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            0x3C => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            0x5C => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            0x7C => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            0xDC => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            0xFC => Some(Self::undocumented(Mnemonic::NOP, AddressingMode::AbsoluteX)),
+            //JAM-----------------------------------------------------------------------------------
+            0x02 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x12 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x22 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x32 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x42 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x52 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x62 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x72 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0x92 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0xB2 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0xD2 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
+            0xF2 => Some(Self::undocumented(Mnemonic::JAM, AddressingMode::Implied)),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,197 @@
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz, set_flags_nz_word};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::variant::CPUVariant;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// A + M + C in binary mode, sets N, Z, C and V.
+fn add_binary(state: &mut CPUState, value: u8) {
+    let carry: u16 = (state.sr & flag!(carry)) as u16;
+    let sum: u16 = state.a as u16 + value as u16 + carry;
+    let result: u8 = sum as u8;
+
+    set_flag(state, flag!(carry), sum > 0xFF);
+    set_flag(state, flag!(overflow), (!(state.a ^ value) & (state.a ^ result) & 0x80) != 0);
+    set_flags_nz(result, state);
+    state.a = result;
+}
+
+/// A + M + C in NMOS decimal mode.
+/// Z is taken from the binary sum, N and V from the intermediate result before the high nibble
+/// is adjusted, as on real hardware. only C and A are valid BCD results.
+fn add_decimal(state: &mut CPUState, value: u8) {
+    let a: i16 = state.a as i16;
+    let m: i16 = value as i16;
+    let carry: i16 = (state.sr & flag!(carry)) as i16;
+
+    let mut lo: i16 = (a & 0x0F) + (m & 0x0F) + carry;
+    if lo >= 0x0A {
+        lo = ((lo + 0x06) & 0x0F) + 0x10;
+    }
+    let mut sum: i16 = (a & 0xF0) + (m & 0xF0) + lo;
+
+    let binary_result: u8 = (a + m + carry) as u8;
+    set_flag(state, flag!(zero), binary_result == 0);
+    set_flag(state, flag!(negative), sum & 0x80 != 0);
+    set_flag(state, flag!(overflow), (!(a ^ m) & (a ^ sum) & 0x80) != 0);
+
+    if sum >= 0xA0 {
+        sum += 0x60;
+    }
+    set_flag(state, flag!(carry), sum >= 0x100);
+    state.a = sum as u8;
+}
+
+/// A - M - (1 - C) in NMOS decimal mode.
+/// all flags are set exactly like binary subtraction, only A receives the BCD result.
+fn subtract_decimal(state: &mut CPUState, value: u8) {
+    let a: i16 = state.a as i16;
+    let m: i16 = value as i16;
+    let carry: i16 = (state.sr & flag!(carry)) as i16;
+
+    let mut lo: i16 = (a & 0x0F) - (m & 0x0F) + carry - 1;
+    if lo < 0 {
+        lo = ((lo - 0x06) & 0x0F) - 0x10;
+    }
+    let mut difference: i16 = (a & 0xF0) - (m & 0xF0) + lo;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+
+    add_binary(state, !value);
+    state.a = difference as u8;
+}
+
+/// A - M - (1 - C) in 65C02 decimal mode.
+/// C and V are set like binary subtraction, N and Z are valid for the BCD result.
+fn subtract_decimal_cmos(state: &mut CPUState, value: u8) {
+    let a: i16 = state.a as i16;
+    let m: i16 = value as i16;
+    let carry: i16 = (state.sr & flag!(carry)) as i16;
+
+    let lo: i16 = (a & 0x0F) - (m & 0x0F) + carry - 1;
+    let mut difference: i16 = a - m + carry - 1;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+    if lo < 0 {
+        difference -= 0x06;
+    }
+
+    add_binary(state, !value);
+    state.a = difference as u8;
+    set_flags_nz(state.a, state);
+}
+
+/// whether ADC/SBC run in decimal mode, which the 2A03 does not have
+fn decimal_mode(state: &CPUState, variant: CPUVariant) -> bool {
+    state.sr & flag!(decimal) != 0 && variant.has_decimal_mode()
+}
+
+/// A + M + C, in decimal mode if D is set
+pub(crate) fn add_with_carry(state: &mut CPUState, value: u8, variant: CPUVariant) {
+    if decimal_mode(state, variant) {
+        add_decimal(state, value);
+        // the 65C02 fixed N and Z to reflect the BCD result
+        if variant.is_cmos() {
+            set_flags_nz(state.a, state);
+        }
+    } else {
+        add_binary(state, value);
+    }
+}
+
+/// A - M - (1 - C), in decimal mode if D is set
+pub(crate) fn subtract_with_borrow(state: &mut CPUState, value: u8, variant: CPUVariant) {
+    if decimal_mode(state, variant) && variant.is_cmos() {
+        subtract_decimal_cmos(state, value);
+    } else if decimal_mode(state, variant) {
+        subtract_decimal(state, value);
+    } else {
+        // binary subtraction is addition of the one's complement
+        add_binary(state, !value);
+    }
+}
+
+/// C + M + carry on the 16-bit accumulator of the 65C816. decimal mode adjusts each of the four
+/// digits, N, Z and V are valid like on the 65C02.
+pub(crate) fn add_with_carry_word(state: &mut CPUState, value: u16) {
+    let a: u32 = state.accumulator() as u32;
+    let m: u32 = value as u32;
+    let carry: u32 = (state.sr & flag!(carry)) as u32;
+
+    let (result, intermediate): (u32, u32) = if state.sr & flag!(decimal) != 0 {
+        let mut sum: u32 = 0;
+        let mut intermediate: u32 = 0;
+        let mut digit_carry: u32 = carry;
+        for shift in [0, 4, 8, 12] {
+            let mut digit: u32 = ((a >> shift) & 0x0F) + ((m >> shift) & 0x0F) + digit_carry;
+            // V is taken before the top digit is adjusted, as in 8-bit decimal mode
+            intermediate = sum | (digit << shift);
+            if digit > 0x09 {
+                digit += 0x06;
+            }
+            digit_carry = (digit > 0x0F) as u32;
+            sum |= (digit & 0x0F) << shift;
+        }
+        (sum | (digit_carry << 16), intermediate)
+    } else {
+        let sum: u32 = a + m + carry;
+        (sum, sum)
+    };
+
+    set_flag(state, flag!(carry), result > 0xFFFF);
+    set_flag(state, flag!(overflow), (!(a ^ m) & (a ^ intermediate) & 0x8000) != 0);
+    set_flags_nz_word(result as u16, state);
+    state.set_accumulator(result as u16);
+}
+
+/// C - M - (1 - carry) on the 16-bit accumulator of the 65C816. C and V are set like binary
+/// subtraction, decimal mode subtracts digit by digit and sets N and Z from the BCD result.
+pub(crate) fn subtract_with_borrow_word(state: &mut CPUState, value: u16) {
+    let a: u16 = state.accumulator();
+    let borrow_in: i32 = 1 - (state.sr & flag!(carry)) as i32;
+    let decimal: bool = state.sr & flag!(decimal) != 0;
+
+    // binary subtraction is addition of the one's complement
+    state.sr &= !flag!(decimal);
+    add_with_carry_word(state, !value);
+    set_flag(state, flag!(decimal), decimal);
+    if !decimal {
+        return;
+    }
+
+    let mut difference: u16 = 0;
+    let mut borrow: i32 = borrow_in;
+    for shift in [0, 4, 8, 12] {
+        let mut digit: i32 = ((a >> shift) & 0x0F) as i32 - ((value >> shift) & 0x0F) as i32 - borrow;
+        borrow = (digit < 0) as i32;
+        if digit < 0 {
+            digit += 10;
+        }
+        difference |= ((digit as u16) & 0x0F) << shift;
+    }
+    state.set_accumulator(difference);
+    set_flags_nz_word(difference, state);
+}
+
+pub fn evaluate_arithmetic<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let value: u8 = cpu.resolve_address_and_get_value(addressing_mode)?;
+    let variant: CPUVariant = cpu.variant();
+    // the 65C02 takes an extra cycle to fix up the flags in decimal mode, the 65C816 does not
+    let decimal_cycles: u8 = (variant.is_cmos() && !variant.is_65816() && decimal_mode(cpu.get_state(), variant)) as u8;
+    let cycles: u8 = cycles_transfer(addressing_mode, cpu.crossed_page_boundary())? + decimal_cycles;
+    let state: &mut CPUState = cpu.state_mut();
+
+    match mnemonic {
+        Mnemonic::ADC => add_with_carry(state, value, variant),
+        Mnemonic::SBC => subtract_with_borrow(state, value, variant),
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    Ok(cycles)
+}
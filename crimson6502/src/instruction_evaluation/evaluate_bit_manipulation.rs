@@ -0,0 +1,46 @@
+use crate::instruction_evaluation::evaluate_shift::cycles_read_modify_write;
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// BBR/BBS take 5 cycles, +1 if the branch is taken and +1 more if it crosses a page
+pub(crate) const fn cycles_branch_on_bit(taken: bool, page_crossed: bool) -> u8 {
+    match (taken, page_crossed) {
+        (false, _) => 5,
+        (true, false) => 6,
+        (true, true) => 7,
+    }
+}
+
+/// RMB/SMB reset or set a bit of a zeropage byte, BBR/BBS branch on it.
+/// the bit number is encoded in bits 4-6 of the opcode. no flags are affected.
+pub fn evaluate_bit_manipulation<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, opcode: u8, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let mask: u8 = 1 << ((opcode >> 4) & 0x07);
+
+    match mnemonic {
+        Mnemonic::RMB | Mnemonic::SMB => {
+            cpu.resolve_address_and_modify_value(addressing_mode, |value| {
+                match mnemonic {
+                    Mnemonic::RMB => value & !mask,
+                    _ => value | mask,
+                }
+            })?;
+            cycles_read_modify_write(addressing_mode)
+        },
+        Mnemonic::BBR | Mnemonic::BBS => {
+            let value: u8 = cpu.resolve_address_and_get_value(AddressingMode::ZeroPage)?;
+            let target: u16 = cpu.resolve_address(AddressingMode::Relative)?;
+            let taken: bool = match mnemonic {
+                Mnemonic::BBR => value & mask == 0,
+                _ => value & mask != 0,
+            };
+            if taken {
+                cpu.state_mut().pc = target;
+            }
+            Ok(cycles_branch_on_bit(taken, cpu.crossed_page_boundary()))
+        },
+        _ => Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    }
+}
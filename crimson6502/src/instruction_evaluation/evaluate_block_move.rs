@@ -0,0 +1,43 @@
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// MVN/MVP take 7 cycles per byte
+pub(crate) const CYCLES_BLOCK_MOVE: u8 = 7;
+
+/// MVN/MVP: copies one byte from the source bank at X to the destination bank at Y and
+/// decrements C. MVN increments X and Y, MVP decrements them. the instruction repeats itself
+/// by rewinding PC until C wraps to 0xFFFF, so interrupts are serviced between bytes.
+/// DBR is left at the destination bank.
+pub fn evaluate_block_move<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, _addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let step: u16 = match mnemonic {
+        Mnemonic::MVN => 1,
+        Mnemonic::MVP => 0xFFFF,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    let destination_bank: u8 = cpu.fetch_byte();
+    let source_bank: u8 = cpu.fetch_byte();
+
+    let source: u32 = ((source_bank as u32) << 16) | cpu.get_state().index_x() as u32;
+    let destination: u32 = ((destination_bank as u32) << 16) | cpu.get_state().index_y() as u32;
+    let value: u8 = cpu.read_byte_long(source);
+    cpu.write_byte_long(destination, value);
+
+    let state: &mut CPUState = cpu.state_mut();
+    // 8-bit index registers wrap inside their low byte
+    let index_mask: u16 = if state.index_16() { 0xFFFF } else { 0x00FF };
+    state.set_index_x(state.index_x().wrapping_add(step) & index_mask);
+    state.set_index_y(state.index_y().wrapping_add(step) & index_mask);
+
+    let count: u16 = state.accumulator().wrapping_sub(1);
+    state.set_accumulator(count);
+    state.dbr = destination_bank;
+    if count != 0xFFFF {
+        state.pc = state.pc.wrapping_sub(3);
+    }
+
+    Ok(CYCLES_BLOCK_MOVE)
+}
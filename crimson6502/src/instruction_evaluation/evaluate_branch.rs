@@ -0,0 +1,53 @@
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// branches take 2 cycles, +1 if taken and +1 more if the target is in another page.
+/// BRL always takes 4 cycles
+pub(crate) const fn cycles_branch(addressing_mode: AddressingMode, taken: bool, page_crossed: bool) -> u8 {
+    match (addressing_mode, taken, page_crossed) {
+        (AddressingMode::RelativeLong, _, _) => 4,
+        (_, false, _) => 2,
+        (_, true, false) => 3,
+        (_, true, true) => 4,
+    }
+}
+
+fn branch_condition(mnemonic: Mnemonic, sr: u8) -> Result<bool, CpuError> {
+    let condition: bool = match mnemonic {
+        Mnemonic::BCC => sr & flag!(carry) == 0,
+        Mnemonic::BCS => sr & flag!(carry) != 0,
+        Mnemonic::BNE => sr & flag!(zero) == 0,
+        Mnemonic::BEQ => sr & flag!(zero) != 0,
+        Mnemonic::BPL => sr & flag!(negative) == 0,
+        Mnemonic::BMI => sr & flag!(negative) != 0,
+        Mnemonic::BVC => sr & flag!(overflow) == 0,
+        Mnemonic::BVS => sr & flag!(overflow) != 0,
+        Mnemonic::BRA | Mnemonic::BRL => true,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    Ok(condition)
+}
+
+pub fn evaluate_branch<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let target: u16 = cpu.resolve_address(addressing_mode)?;
+
+    let taken: bool = branch_condition(mnemonic, cpu.get_state().sr)?;
+    if taken {
+        // a taken branch reads the next opcode while adding the offset, and the address with
+        // the uncorrected high byte while fixing a page crossing
+        if let AddressingMode::Relative = addressing_mode {
+            let next_instruction_addr: u16 = cpu.get_state().pc;
+            let program_bank: u32 = (cpu.get_state().pbr as u32) << 16;
+            cpu.dummy_read(program_bank | next_instruction_addr as u32);
+            if cpu.crossed_page_boundary() {
+                cpu.dummy_read(program_bank | ((next_instruction_addr & 0xFF00) | (target & 0x00FF)) as u32);
+            }
+        }
+        cpu.state_mut().pc = target;
+    }
+
+    Ok(cycles_branch(addressing_mode, taken, cpu.crossed_page_boundary()))
+}
@@ -0,0 +1,36 @@
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz, set_flags_nz_word};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// sets N, Z and C according to register - value, without storing the result.
+pub(crate) fn set_flags_compare(register: u8, value: u8, state: &mut CPUState) {
+    set_flags_nz(register.wrapping_sub(value), state);
+    set_flag(state, flag!(carry), register >= value);
+}
+
+/// 16-bit counterpart of set_flags_compare for the 65C816.
+pub(crate) fn set_flags_compare_word(register: u16, value: u16, state: &mut CPUState) {
+    set_flags_nz_word(register.wrapping_sub(value), state);
+    set_flag(state, flag!(carry), register >= value);
+}
+
+pub fn evaluate_compare<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let value: u8 = cpu.resolve_address_and_get_value(addressing_mode)?;
+    let cycles: u8 = cycles_transfer(addressing_mode, cpu.crossed_page_boundary())?;
+    let state: &mut CPUState = cpu.state_mut();
+
+    let register: u8 = match mnemonic {
+        Mnemonic::CMP => state.a,
+        Mnemonic::CPX => state.x,
+        Mnemonic::CPY => state.y,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    set_flags_compare(register, value, state);
+
+    Ok(cycles)
+}
@@ -0,0 +1,53 @@
+use crate::instruction_evaluation::flags::set_flag;
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+pub(crate) const fn cycles_flag(mnemonic: Mnemonic) -> u8 {
+    match mnemonic {
+        Mnemonic::REP | Mnemonic::SEP => 3,
+        _ => 2,
+    }
+}
+
+pub fn evaluate_flag<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    // REP and SEP take the flags to change as operand. M and X stay set in emulation mode
+    let operand: u8 = match mnemonic {
+        Mnemonic::REP | Mnemonic::SEP => cpu.resolve_address_and_get_value(addressing_mode)?,
+        _ => 0,
+    };
+    let state: &mut CPUState = cpu.state_mut();
+
+    match mnemonic {
+        Mnemonic::CLC => set_flag(state, flag!(carry), false),
+        Mnemonic::SEC => set_flag(state, flag!(carry), true),
+        Mnemonic::CLI => set_flag(state, flag!(interrupt), false),
+        Mnemonic::SEI => set_flag(state, flag!(interrupt), true),
+        Mnemonic::CLD => set_flag(state, flag!(decimal), false),
+        Mnemonic::SED => set_flag(state, flag!(decimal), true),
+        Mnemonic::CLV => set_flag(state, flag!(overflow), false),
+        Mnemonic::REP => {
+            state.sr &= !operand;
+            state.update_register_widths();
+        },
+        Mnemonic::SEP => {
+            state.sr |= operand;
+            state.update_register_widths();
+        },
+        // exchanges C and E. both directions come out with 8-bit registers
+        Mnemonic::XCE => {
+            let carry: bool = state.sr & flag!(carry) != 0;
+            let emulation: bool = state.e;
+            set_flag(state, flag!(carry), emulation);
+            state.e = carry;
+            state.sr |= flag!(memory) | flag!(index);
+            state.update_register_widths();
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    Ok(cycles_flag(mnemonic))
+}
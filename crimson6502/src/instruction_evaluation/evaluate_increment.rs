@@ -0,0 +1,41 @@
+use crate::instruction_evaluation::evaluate_shift::cycles_read_modify_write;
+use crate::instruction_evaluation::flags::set_flags_nz;
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+pub(crate) const fn cycles_increment(mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::INC | Mnemonic::DEC => cycles_read_modify_write(addressing_mode),
+        _ => Ok(2),
+    }
+}
+
+pub fn evaluate_increment<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::INC | Mnemonic::DEC => {
+            let result: u8 = cpu.resolve_address_and_modify_value(addressing_mode, |value| {
+                match mnemonic {
+                    Mnemonic::INC => value.wrapping_add(1),
+                    _ => value.wrapping_sub(1),
+                }
+            })?;
+            set_flags_nz(result, cpu.state_mut());
+        },
+        Mnemonic::INX | Mnemonic::INY | Mnemonic::DEX | Mnemonic::DEY => {
+            let state: &mut CPUState = cpu.state_mut();
+            match mnemonic {
+                Mnemonic::INX => {state.x = state.x.wrapping_add(1); set_flags_nz(state.x, state)},
+                Mnemonic::INY => {state.y = state.y.wrapping_add(1); set_flags_nz(state.y, state)},
+                Mnemonic::DEX => {state.x = state.x.wrapping_sub(1); set_flags_nz(state.x, state)},
+                _ => {state.y = state.y.wrapping_sub(1); set_flags_nz(state.y, state)},
+            };
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    cycles_increment(mnemonic, addressing_mode)
+}
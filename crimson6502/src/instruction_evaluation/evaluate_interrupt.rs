@@ -0,0 +1,54 @@
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::{CPUState, COP_VECTOR, IRQ_VECTOR};
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// 65C816 native mode takes a cycle more to push or pull the program bank
+pub(crate) const fn cycles_interrupt(mnemonic: Mnemonic, native: bool) -> Result<u8, CpuError> {
+    let cycles: u8 = match mnemonic {
+        Mnemonic::BRK | Mnemonic::COP => 7,
+        Mnemonic::RTI => 6,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    Ok(cycles + native as u8)
+}
+
+pub fn evaluate_interrupt<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, _addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let native: bool = !cpu.get_state().e;
+    let cycles: u8 = cycles_interrupt(mnemonic, native)?;
+    match mnemonic {
+        Mnemonic::BRK => {
+            // BRK is followed by a padding byte, the pushed return address skips it
+            cpu.fetch_byte();
+            cpu.enter_interrupt(IRQ_VECTOR, true);
+        },
+        Mnemonic::COP => {
+            // COP is followed by a signature byte, the pushed return address skips it
+            cpu.fetch_byte();
+            cpu.enter_interrupt(COP_VECTOR, false);
+        },
+        Mnemonic::RTI => {
+            cpu.dummy_read_stack();
+            let pulled_sr: u8 = cpu.pull_byte();
+            let pulled_pc: u16 = cpu.pull_word();
+            let pulled_pbr: u8 = if native { cpu.pull_byte() } else { 0 };
+
+            let state: &mut CPUState = cpu.state_mut();
+            if native {
+                state.sr = pulled_sr;
+                state.pbr = pulled_pbr;
+                state.update_register_widths();
+            } else {
+                // B and unused are ignored when pulling, the register keeps its own bits
+                let ignored_bits: u8 = flag!(brk) | flag!(unused);
+                state.sr = (pulled_sr & !ignored_bits) | (state.sr & ignored_bits);
+            }
+            state.pc = pulled_pc;
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    Ok(cycles)
+}
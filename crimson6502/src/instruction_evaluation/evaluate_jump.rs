@@ -0,0 +1,76 @@
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::util::bytes_to_word;
+use crate::variant::CPUVariant;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+pub(crate) const fn cycles_jump(mnemonic: Mnemonic, addressing_mode: AddressingMode, variant: CPUVariant) -> Result<u8, CpuError> {
+    let cycles: u8 = match (mnemonic, addressing_mode) {
+        (Mnemonic::JMP, AddressingMode::Absolute) => 3,
+        // the 65C02 spends a cycle fixing the page wrap bug, the 65C816 does not need it
+        (Mnemonic::JMP, AddressingMode::Indirect) if variant.is_cmos() && !variant.is_65816() => 6,
+        (Mnemonic::JMP, AddressingMode::Indirect) => 5,
+        (Mnemonic::JMP, AddressingMode::AbsoluteIndirectX) => 6,
+        (Mnemonic::JSR, AddressingMode::Absolute) => 6,
+        (Mnemonic::JSR, AddressingMode::AbsoluteIndirectX) => 8,
+        (Mnemonic::RTS, AddressingMode::Implied) => 6,
+        (Mnemonic::JML, AddressingMode::AbsoluteLong) => 4,
+        (Mnemonic::JML, AddressingMode::AbsoluteIndirectLong) => 6,
+        (Mnemonic::JSL, AddressingMode::AbsoluteLong) => 8,
+        (Mnemonic::RTL, AddressingMode::Implied) => 6,
+        (Mnemonic::JMP | Mnemonic::JSR | Mnemonic::RTS | Mnemonic::JML | Mnemonic::JSL | Mnemonic::RTL, _)
+            => return Err(CpuError::UnsupportedAddressingMode(addressing_mode)),
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    Ok(cycles)
+}
+
+pub fn evaluate_jump<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let cycles: u8 = cycles_jump(mnemonic, addressing_mode, cpu.variant())?;
+    // jumps within the program bank keep PBR, the long forms replace it
+    let target: u32 = match mnemonic {
+        Mnemonic::JMP | Mnemonic::JML => cpu.resolve_address_long(addressing_mode)?,
+        // JSR reads the low byte of the target, pushes PC while it points at the high byte and
+        // only then reads the high byte
+        Mnemonic::JSR if addressing_mode == AddressingMode::Absolute => {
+            let lo: u8 = cpu.fetch_byte();
+            cpu.dummy_read_stack();
+            cpu.push_word(cpu.get_state().pc);
+            let hi: u8 = cpu.fetch_byte();
+            ((cpu.get_state().pbr as u32) << 16) | bytes_to_word(lo, hi) as u32
+        },
+        Mnemonic::JSR | Mnemonic::JSL => {
+            // the return address is pushed before the operand is fully read, so it is computed
+            // from the address of the last operand byte
+            let return_addr: u16 = cpu.get_state().pc.wrapping_add(addressing_mode.instruction_length() as u16 - 1);
+            if let Mnemonic::JSL = mnemonic {
+                cpu.push_byte(cpu.get_state().pbr);
+            }
+            cpu.push_word(return_addr);
+            cpu.resolve_address_long(addressing_mode)?
+        },
+        // the last cycle of RTS reads the pulled address while incrementing it
+        Mnemonic::RTS => {
+            cpu.dummy_read_stack();
+            let pulled_pc: u32 = ((cpu.get_state().pbr as u32) << 16) | cpu.pull_word() as u32;
+            cpu.dummy_read(pulled_pc);
+            (pulled_pc & 0xFF_0000) | (pulled_pc as u16).wrapping_add(1) as u32
+        },
+        Mnemonic::RTL => {
+            cpu.dummy_read_stack();
+            let pc: u16 = cpu.pull_word().wrapping_add(1);
+            let bank: u8 = cpu.pull_byte();
+            ((bank as u32) << 16) | pc as u32
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    let state: &mut CPUState = cpu.state_mut();
+    state.pc = target as u16;
+    state.pbr = (target >> 16) as u8;
+
+    Ok(cycles)
+}
@@ -0,0 +1,66 @@
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction_evaluation::evaluate_shift::cycles_read_modify_write;
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// BIT copies bits 7 and 6 of memory into N and V, and sets Z from A & M. A is unchanged.
+fn set_flags_bit(value: u8, state: &mut CPUState) {
+    set_flag(state, flag!(zero), state.a & value == 0);
+    set_flag(state, flag!(negative), value & flag!(negative) != 0);
+    set_flag(state, flag!(overflow), value & flag!(overflow) != 0);
+}
+
+/// TRB/TSB: read-modify-write that clears or sets the bits of A in memory, Z from A & M.
+fn evaluate_test_bits<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let a: u8 = cpu.get_state().a;
+    let mut tested: u8 = 0;
+
+    cpu.resolve_address_and_modify_value(addressing_mode, |value| {
+        tested = a & value;
+        match mnemonic {
+            Mnemonic::TRB => value & !a,
+            _ => value | a,
+        }
+    })?;
+
+    set_flag(cpu.state_mut(), flag!(zero), tested == 0);
+
+    cycles_read_modify_write(addressing_mode)
+}
+
+pub(crate) const fn cycles_logical(mnemonic: Mnemonic, addressing_mode: AddressingMode, page_crossed: bool) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::TRB | Mnemonic::TSB => cycles_read_modify_write(addressing_mode),
+        _ => cycles_transfer(addressing_mode, page_crossed),
+    }
+}
+
+pub fn evaluate_logical<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    if let Mnemonic::TRB | Mnemonic::TSB = mnemonic {
+        return evaluate_test_bits(cpu, mnemonic, addressing_mode);
+    }
+
+    let value: u8 = cpu.resolve_address_and_get_value(addressing_mode)?;
+    let cycles: u8 = cycles_logical(mnemonic, addressing_mode, cpu.crossed_page_boundary())?;
+    let state: &mut CPUState = cpu.state_mut();
+
+    match mnemonic {
+        Mnemonic::AND => {state.a &= value; set_flags_nz(state.a, state)},
+        Mnemonic::ORA => {state.a |= value; set_flags_nz(state.a, state)},
+        Mnemonic::EOR => {state.a ^= value; set_flags_nz(state.a, state)},
+        // the 65C02 immediate form has no memory operand to take N and V from
+        Mnemonic::BIT if addressing_mode == AddressingMode::Immediate => {
+            let zero: bool = state.a & value == 0;
+            set_flag(state, flag!(zero), zero)
+        },
+        Mnemonic::BIT => set_flags_bit(value, state),
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    Ok(cycles)
+}
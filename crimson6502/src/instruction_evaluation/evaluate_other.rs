@@ -1,10 +1,41 @@
-use crate::instruction_evaluation::types::InstructionResult;
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::variant::CPUVariant;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
 use crate::CPU;
 
-pub fn evaluate_nop(cpu: &CPU) -> InstructionResult {
-    InstructionResult::new(
-        cpu.get_state().clone(),
-        2,
-        0,
-    ) 
-}
\ No newline at end of file
+/// the 65C02 NOPs in columns 3, 7, B and F take a single cycle, $5C takes eight
+pub(crate) const fn cycles_nop(opcode: u8, addressing_mode: AddressingMode, variant: CPUVariant, page_crossed: bool) -> Result<u8, CpuError> {
+    let cmos: bool = variant.is_cmos();
+    match addressing_mode {
+        AddressingMode::Implied if cmos && opcode & 0x03 == 0x03 => Ok(1),
+        AddressingMode::Implied => Ok(2),
+        AddressingMode::Absolute if cmos && opcode == 0x5C => Ok(8),
+        _ => cycles_transfer(addressing_mode, page_crossed),
+    }
+}
+
+/// NOP. the undocumented variants with an operand still perform the read of their addressing mode.
+pub fn evaluate_nop<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, opcode: u8, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    if addressing_mode != AddressingMode::Implied {
+        cpu.resolve_address_and_get_value(addressing_mode)?;
+    }
+
+    cycles_nop(opcode, addressing_mode, cpu.variant(), cpu.crossed_page_boundary())
+}
+
+/// WAI and STP take 3 cycles before they halt
+pub(crate) const CYCLES_HALT: u8 = 3;
+
+/// WAI and STP halt the CPU, see `CPU::run_state`
+pub fn evaluate_halt<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, _addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::WAI => cpu.wait(),
+        Mnemonic::STP => cpu.stop(),
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    Ok(CYCLES_HALT)
+}
@@ -0,0 +1,59 @@
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::variant::CPUVariant;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// cycles of read-modify-write instructions. indexed modes always pay for the page cross.
+pub(crate) const fn cycles_read_modify_write(addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let cycles: u8 = match addressing_mode {
+        AddressingMode::Accumulator => 2,
+        AddressingMode::ZeroPage => 5,
+        AddressingMode::ZeroPageX => 6,
+        AddressingMode::Absolute => 6,
+        AddressingMode::AbsoluteX => 7,
+        // only reachable by undocumented opcodes
+        AddressingMode::AbsoluteY => 7,
+        AddressingMode::IndirectX | AddressingMode::IndirectY => 8,
+        // invalid
+        _ => return Err(CpuError::UnsupportedAddressingMode(addressing_mode)),
+    };
+    Ok(cycles)
+}
+
+/// the 65C02 only pays for the page cross when it happens, the 65C816 always does
+pub(crate) const fn cycles_shift(addressing_mode: AddressingMode, variant: CPUVariant, page_crossed: bool) -> Result<u8, CpuError> {
+    match addressing_mode {
+        AddressingMode::AbsoluteX if variant.is_cmos() && !variant.is_65816() && !page_crossed => Ok(6),
+        _ => cycles_read_modify_write(addressing_mode),
+    }
+}
+
+pub fn evaluate_shift<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    if !matches!(mnemonic, Mnemonic::ASL | Mnemonic::LSR | Mnemonic::ROL | Mnemonic::ROR) {
+        return Err(CpuError::UnsupportedMnemonic(mnemonic));
+    }
+
+    let carry_in: u8 = cpu.get_state().sr & flag!(carry);
+    let mut carry_out: bool = false;
+
+    let result: u8 = cpu.resolve_address_and_modify_value(addressing_mode, |value| {
+        match mnemonic {
+            Mnemonic::ASL => {carry_out = value & 0x80 != 0; value << 1},
+            Mnemonic::LSR => {carry_out = value & 0x01 != 0; value >> 1},
+            Mnemonic::ROL => {carry_out = value & 0x80 != 0; (value << 1) | carry_in},
+            Mnemonic::ROR => {carry_out = value & 0x01 != 0; (value >> 1) | (carry_in << 7)},
+            _ => value,
+        }
+    })?;
+
+    let cycles: u8 = cycles_shift(addressing_mode, cpu.variant(), cpu.crossed_page_boundary())?;
+    let state: &mut CPUState = cpu.state_mut();
+    set_flag(state, flag!(carry), carry_out);
+    set_flags_nz(result, state);
+
+    Ok(cycles)
+}
@@ -0,0 +1,87 @@
+use crate::instruction_evaluation::flags::{set_flags_nz, set_flags_nz_word};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+pub(crate) const fn cycles_stack(mnemonic: Mnemonic) -> Result<u8, CpuError> {
+    let cycles: u8 = match mnemonic {
+        Mnemonic::PHA | Mnemonic::PHP | Mnemonic::PHX | Mnemonic::PHY | Mnemonic::PHB | Mnemonic::PHK => 3,
+        Mnemonic::PLA | Mnemonic::PLP | Mnemonic::PLX | Mnemonic::PLY | Mnemonic::PLB | Mnemonic::PHD => 4,
+        Mnemonic::PLD | Mnemonic::PEA => 5,
+        Mnemonic::PEI | Mnemonic::PER => 6,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+    Ok(cycles)
+}
+
+pub fn evaluate_stack<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let native: bool = !cpu.get_state().e;
+    let cycles: u8 = cycles_stack(mnemonic)?;
+    let mut pulled_value: u8 = 0;
+    let mut pulled_word: u16 = 0;
+    match mnemonic {
+        Mnemonic::PHA => cpu.push_byte(cpu.get_state().a),
+        // B and unused do not exist in the register, the pushed copy always has both set.
+        // in 65C816 native mode they are M and X and pushed as they are
+        Mnemonic::PHP if native => cpu.push_byte(cpu.get_state().sr),
+        Mnemonic::PHP => cpu.push_byte(cpu.get_state().sr | flag!(brk) | flag!(unused)),
+        Mnemonic::PHX => cpu.push_byte(cpu.get_state().x),
+        Mnemonic::PHY => cpu.push_byte(cpu.get_state().y),
+        Mnemonic::PHB => cpu.push_byte(cpu.get_state().dbr),
+        Mnemonic::PHK => cpu.push_byte(cpu.get_state().pbr),
+        Mnemonic::PHD => cpu.push_word(cpu.get_state().d),
+        // PEA and PEI push the effective address of their operand, PER the branch target
+        Mnemonic::PEA | Mnemonic::PEI | Mnemonic::PER => {
+            let addr: u16 = cpu.resolve_address(addressing_mode)?;
+            cpu.push_word(addr);
+        },
+        Mnemonic::PLA | Mnemonic::PLP | Mnemonic::PLX | Mnemonic::PLY | Mnemonic::PLB => {
+            cpu.dummy_read_stack();
+            pulled_value = cpu.pull_byte();
+        },
+        Mnemonic::PLD => {
+            cpu.dummy_read_stack();
+            pulled_word = cpu.pull_word();
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    let state: &mut CPUState = cpu.state_mut();
+    match mnemonic {
+        Mnemonic::PLA => {
+            state.a = pulled_value;
+            set_flags_nz(pulled_value, state);
+        },
+        Mnemonic::PLX => {
+            state.x = pulled_value;
+            set_flags_nz(pulled_value, state);
+        },
+        Mnemonic::PLY => {
+            state.y = pulled_value;
+            set_flags_nz(pulled_value, state);
+        },
+        Mnemonic::PLB => {
+            state.dbr = pulled_value;
+            set_flags_nz(pulled_value, state);
+        },
+        Mnemonic::PLD => {
+            state.d = pulled_word;
+            set_flags_nz_word(pulled_word, state);
+        },
+        Mnemonic::PLP if native => {
+            state.sr = pulled_value;
+            state.update_register_widths();
+        },
+        Mnemonic::PLP => {
+            // B and unused are ignored when pulling, the register keeps its own bits
+            let ignored_bits: u8 = flag!(brk) | flag!(unused);
+            state.sr = (pulled_value & !ignored_bits) | (state.sr & ignored_bits);
+        },
+        _ => (),
+    };
+
+    Ok(cycles)
+}
@@ -1,10 +1,13 @@
-use crate::instruction_evaluation::types::InstructionResult;
+use crate::instruction_evaluation::flags::{set_flags_nz, set_flags_nz_word};
 use crate::instruction::{AddressingMode, Mnemonic};
 use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
 use crate::CPU;
 
-fn cycles_transfer(addressing_mode: AddressingMode, page_crossed: bool) -> u8 {
-    match addressing_mode {
+pub(crate) const fn cycles_transfer(addressing_mode: AddressingMode, page_crossed: bool) -> Result<u8, CpuError> {
+    let cycles: u8 = match addressing_mode {
         AddressingMode::Immediate => 2,
         AddressingMode::ZeroPage => 3,
         AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => 4,
@@ -14,80 +17,84 @@ fn cycles_transfer(addressing_mode: AddressingMode, page_crossed: bool) -> u8 {
         AddressingMode::IndirectX => 6,
         AddressingMode::IndirectY if page_crossed => 6,
         AddressingMode::IndirectY => 5,
+        AddressingMode::ZeroPageIndirect => 5,
+        AddressingMode::AbsoluteLong | AddressingMode::AbsoluteLongX => 5,
+        AddressingMode::ZeroPageIndirectLong | AddressingMode::ZeroPageIndirectLongY => 6,
+        AddressingMode::StackRelative => 4,
+        AddressingMode::StackRelativeIndirectY => 7,
         // invalid
-        AddressingMode::Implied => panic!()
-    }
+        AddressingMode::Implied | AddressingMode::Accumulator | AddressingMode::Relative | AddressingMode::Indirect
+        | AddressingMode::AbsoluteIndirectX | AddressingMode::ZeroPageRelative | AddressingMode::RelativeLong
+        | AddressingMode::AbsoluteIndirectLong | AddressingMode::BlockMove
+            => return Err(CpuError::UnsupportedAddressingMode(addressing_mode)),
+    };
+    Ok(cycles)
 }
 
-fn set_flags_transfer(value: u8, state: &mut CPUState) {
-    if value == 0 {
-        state.sr |= flag!(zero);
-    } else {
-        state.sr &= !flag!(zero);
-    }
-    if value & 0x80 != 0 {
-        state.sr |= flag!(negative);
-    } else {
-        state.sr &= !flag!(negative);
+pub(crate) const fn cycles_register_transfer(mnemonic: Mnemonic) -> u8 {
+    match mnemonic {
+        Mnemonic::XBA => 3,
+        _ => 2,
     }
 }
 
-pub fn evaluate_load(cpu: &mut CPU, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> InstructionResult {
-    let value: u8 = cpu.resolve_address_and_get_value(addressing_mode);
-
-    let mut result = InstructionResult::new(
-        CPUState {
-            ..*cpu.get_state()
-        },
-        cycles_transfer(addressing_mode, cpu.crossed_page_boundary()),
-        addressing_mode.instruction_length(),
-    );
-
-    set_flags_transfer(value, &mut result.state);
+pub fn evaluate_load<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let value: u8 = cpu.resolve_address_and_get_value(addressing_mode)?;
+    let cycles: u8 = cycles_transfer(addressing_mode, cpu.crossed_page_boundary())?;
+    let state: &mut CPUState = cpu.state_mut();
 
     match mnemonic {
-        Mnemonic::LDA => result.state.a = value,
-        Mnemonic::LDX => result.state.x = value,
-        Mnemonic::LDY => result.state.y = value,
-        _ => panic!("evaluate_transfer received invalid mnemonic: {:?}", mnemonic),
+        Mnemonic::LDA => state.a = value,
+        Mnemonic::LDX => state.x = value,
+        Mnemonic::LDY => state.y = value,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
     };
+    set_flags_nz(value, state);
 
-    result
+    Ok(cycles)
 }
 
-pub fn evaluate_store(cpu: &mut CPU, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> InstructionResult {
+pub fn evaluate_store<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
     match mnemonic {
-        Mnemonic::STA => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().a),
-        Mnemonic::STX => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().x),
-        Mnemonic::STY => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().y),
-        _ => panic!("evaluate_store received invalid mnemonic: {:?}", mnemonic),
+        Mnemonic::STA => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().a)?,
+        Mnemonic::STX => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().x)?,
+        Mnemonic::STY => cpu.resolve_address_and_set_value(addressing_mode, cpu.get_state().y)?,
+        Mnemonic::STZ => cpu.resolve_address_and_set_value(addressing_mode, 0)?,
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
     };
 
-    InstructionResult::new(
-        CPUState {
-            ..*cpu.get_state()
-        },
-        cycles_transfer(addressing_mode, true),
-        addressing_mode.instruction_length(),
-    )
+    cycles_transfer(addressing_mode, true)
 }
 
-pub fn evaluate_transfer(cpu: &mut CPU, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> InstructionResult {
-    let mut new_state: CPUState = cpu.get_state().clone();
+pub fn evaluate_transfer<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, _addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let state: &mut CPUState = cpu.state_mut();
 
     match mnemonic {
-        Mnemonic::TAX => {new_state.x = new_state.a; set_flags_transfer(new_state.a, &mut new_state)},
-        Mnemonic::TAY => {new_state.y = new_state.a; set_flags_transfer(new_state.a, &mut new_state)},
-        Mnemonic::TSX => {new_state.x = new_state.s; set_flags_transfer(new_state.s, &mut new_state)},
-        Mnemonic::TXA => {new_state.a = new_state.x; set_flags_transfer(new_state.x, &mut new_state)},
-        Mnemonic::TXS => {new_state.s = new_state.x},
-        Mnemonic::TYA => {new_state.a = new_state.y; set_flags_transfer(new_state.y, &mut new_state)},
-        _ => panic!("evaluate_transfer received invalid mnemonic: {:?}", mnemonic),
+        Mnemonic::TAX => {state.x = state.a; set_flags_nz(state.a, state)},
+        Mnemonic::TAY => {state.y = state.a; set_flags_nz(state.a, state)},
+        Mnemonic::TSX => {state.x = state.s; set_flags_nz(state.s, state)},
+        Mnemonic::TXA => {state.a = state.x; set_flags_nz(state.x, state)},
+        Mnemonic::TXS => {state.s = state.x},
+        Mnemonic::TYA => {state.a = state.y; set_flags_nz(state.y, state)},
+        // 65C816, 8-bit index registers
+        Mnemonic::TXY => {state.y = state.x; set_flags_nz(state.x, state)},
+        Mnemonic::TYX => {state.x = state.y; set_flags_nz(state.y, state)},
+        // 65C816, D and S are always transferred to and from the full 16-bit C
+        Mnemonic::TCD => {state.d = state.accumulator(); set_flags_nz_word(state.d, state)},
+        Mnemonic::TDC => {state.set_accumulator(state.d); set_flags_nz_word(state.d, state)},
+        Mnemonic::TCS => state.set_stack_pointer(state.accumulator()),
+        Mnemonic::TSC => {
+            let stack_pointer: u16 = state.stack_pointer();
+            state.set_accumulator(stack_pointer);
+            set_flags_nz_word(stack_pointer, state)
+        },
+        // 65C816, swaps A and B, N and Z reflect the new A
+        Mnemonic::XBA => {
+            (state.a, state.b) = (state.b, state.a);
+            set_flags_nz(state.a, state)
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
     };
 
-    InstructionResult::new(
-        new_state, 
-        2, 
-        addressing_mode.instruction_length()
-    )
+    Ok(cycles_register_transfer(mnemonic))
 }
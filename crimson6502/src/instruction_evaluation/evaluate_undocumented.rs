@@ -0,0 +1,194 @@
+use crate::instruction_evaluation::evaluate_arithmetic::{add_with_carry, subtract_with_borrow};
+use crate::instruction_evaluation::evaluate_compare::set_flags_compare;
+use crate::instruction_evaluation::evaluate_shift::cycles_read_modify_write;
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::variant::CPUVariant;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// magic constant of the unstable ANE and LXA opcodes, the most common value on real chips
+const UNSTABLE_MAGIC: u8 = 0xEE;
+
+/// SLO, RLA, SRE, RRA, DCP, ISC: a shift, rotate, increment or decrement on memory,
+/// followed by an accumulator operation on the result.
+fn evaluate_combined_read_modify_write<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<(), CpuError> {
+    let carry_in: u8 = cpu.get_state().sr & flag!(carry);
+    let mut carry_out: bool = false;
+
+    let result: u8 = cpu.resolve_address_and_modify_value(addressing_mode, |value| {
+        match mnemonic {
+            Mnemonic::SLO => {carry_out = value & 0x80 != 0; value << 1},
+            Mnemonic::RLA => {carry_out = value & 0x80 != 0; (value << 1) | carry_in},
+            Mnemonic::SRE => {carry_out = value & 0x01 != 0; value >> 1},
+            Mnemonic::RRA => {carry_out = value & 0x01 != 0; (value >> 1) | (carry_in << 7)},
+            Mnemonic::DCP => value.wrapping_sub(1),
+            _ => value.wrapping_add(1),
+        }
+    })?;
+
+    let variant: CPUVariant = cpu.variant();
+    let state: &mut CPUState = cpu.state_mut();
+    match mnemonic {
+        Mnemonic::SLO => {set_flag(state, flag!(carry), carry_out); state.a |= result; set_flags_nz(state.a, state)},
+        Mnemonic::RLA => {set_flag(state, flag!(carry), carry_out); state.a &= result; set_flags_nz(state.a, state)},
+        Mnemonic::SRE => {set_flag(state, flag!(carry), carry_out); state.a ^= result; set_flags_nz(state.a, state)},
+        Mnemonic::RRA => {set_flag(state, flag!(carry), carry_out); add_with_carry(state, result, variant)},
+        Mnemonic::DCP => set_flags_compare(state.a, result, state),
+        _ => subtract_with_borrow(state, result, variant),
+    };
+    Ok(())
+}
+
+/// ARR: AND with the operand, then ROR A with odd C and V. decimal mode adds a BCD fixup,
+/// except on variants without decimal mode.
+fn and_rotate_right(state: &mut CPUState, value: u8, variant: CPUVariant) {
+    let anded: u8 = state.a & value;
+    let carry_in: u8 = state.sr & flag!(carry);
+    let result: u8 = (anded >> 1) | (carry_in << 7);
+
+    if state.sr & flag!(decimal) == 0 || !variant.has_decimal_mode() {
+        set_flags_nz(result, state);
+        set_flag(state, flag!(carry), result & 0x40 != 0);
+        set_flag(state, flag!(overflow), ((result >> 6) ^ (result >> 5)) & 0x01 != 0);
+        state.a = result;
+        return;
+    }
+
+    set_flag(state, flag!(negative), carry_in != 0);
+    set_flag(state, flag!(zero), result == 0);
+    set_flag(state, flag!(overflow), (anded ^ result) & 0x40 != 0);
+
+    let mut adjusted: u8 = result;
+    let lo: u8 = anded & 0x0F;
+    let hi: u8 = anded >> 4;
+    if lo + (lo & 0x01) > 0x05 {
+        adjusted = (adjusted & 0xF0) | (adjusted.wrapping_add(0x06) & 0x0F);
+    }
+    let carry_out: bool = hi + (hi & 0x01) > 0x05;
+    if carry_out {
+        adjusted = adjusted.wrapping_add(0x60);
+    }
+    set_flag(state, flag!(carry), carry_out);
+    state.a = adjusted;
+}
+
+/// SHA, SHX, SHY, TAS: store value & (high byte of the base address + 1).
+/// when indexing crosses a page, the stored value also replaces the high byte of the address.
+fn store_high_byte_and<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, addressing_mode: AddressingMode, value: u8) -> Result<(), CpuError> {
+    let index: u8 = match addressing_mode {
+        AddressingMode::AbsoluteX => cpu.get_state().x,
+        _ => cpu.get_state().y,
+    };
+    let addr: u16 = cpu.resolve_address_for_write(addressing_mode)? as u16;
+    let base_addr: u16 = addr.wrapping_sub(index as u16);
+    let stored_value: u8 = value & ((base_addr >> 8) as u8).wrapping_add(1);
+
+    let target_addr: u16 = if cpu.crossed_page_boundary() {
+        ((stored_value as u16) << 8) | (addr & 0x00FF)
+    } else {
+        addr
+    };
+    cpu.write_byte(target_addr, stored_value);
+    Ok(())
+}
+
+pub(crate) const fn cycles_undocumented(mnemonic: Mnemonic, addressing_mode: AddressingMode, page_crossed: bool) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::SLO | Mnemonic::RLA | Mnemonic::SRE | Mnemonic::RRA | Mnemonic::DCP | Mnemonic::ISC
+            => cycles_read_modify_write(addressing_mode),
+        Mnemonic::SAX | Mnemonic::SHA | Mnemonic::SHX | Mnemonic::SHY | Mnemonic::TAS
+            => cycles_transfer(addressing_mode, true),
+        Mnemonic::LAX | Mnemonic::LAS | Mnemonic::ANC | Mnemonic::ALR | Mnemonic::ARR
+        | Mnemonic::SBX | Mnemonic::ANE | Mnemonic::LXA => cycles_transfer(addressing_mode, page_crossed),
+        Mnemonic::JAM => Ok(2),
+        _ => Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    }
+}
+
+pub fn evaluate_undocumented<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::SLO | Mnemonic::RLA | Mnemonic::SRE | Mnemonic::RRA | Mnemonic::DCP | Mnemonic::ISC => {
+            evaluate_combined_read_modify_write(cpu, mnemonic, addressing_mode)?;
+        },
+        Mnemonic::SAX => {
+            let value: u8 = cpu.get_state().a & cpu.get_state().x;
+            cpu.resolve_address_and_set_value(addressing_mode, value)?;
+        },
+        Mnemonic::SHA | Mnemonic::SHX | Mnemonic::SHY | Mnemonic::TAS => {
+            let state: CPUState = *cpu.get_state();
+            let value: u8 = match mnemonic {
+                Mnemonic::SHA | Mnemonic::TAS => state.a & state.x,
+                Mnemonic::SHX => state.x,
+                _ => state.y,
+            };
+            store_high_byte_and(cpu, addressing_mode, value)?;
+            // TAS also transfers A & X to S
+            if let Mnemonic::TAS = mnemonic {
+                cpu.state_mut().s = value;
+            }
+        },
+        Mnemonic::LAX | Mnemonic::LAS | Mnemonic::ANC | Mnemonic::ALR | Mnemonic::ARR
+        | Mnemonic::SBX | Mnemonic::ANE | Mnemonic::LXA => {
+            let value: u8 = cpu.resolve_address_and_get_value(addressing_mode)?;
+            let variant: CPUVariant = cpu.variant();
+            let state: &mut CPUState = cpu.state_mut();
+            match mnemonic {
+                Mnemonic::LAX => {
+                    state.a = value;
+                    state.x = value;
+                    set_flags_nz(value, state);
+                },
+                Mnemonic::LAS => {
+                    let result: u8 = value & state.s;
+                    state.a = result;
+                    state.x = result;
+                    state.s = result;
+                    set_flags_nz(result, state);
+                },
+                Mnemonic::ANC => {
+                    // C receives bit 7 of the result, like N
+                    let result: u8 = state.a & value;
+                    state.a = result;
+                    set_flags_nz(result, state);
+                    set_flag(state, flag!(carry), result & 0x80 != 0);
+                },
+                Mnemonic::ALR => {
+                    let anded: u8 = state.a & value;
+                    state.a = anded >> 1;
+                    set_flag(state, flag!(carry), anded & 0x01 != 0);
+                    set_flags_nz(state.a, state);
+                },
+                Mnemonic::ARR => and_rotate_right(state, value, variant),
+                Mnemonic::SBX => {
+                    let anded: u8 = state.a & state.x;
+                    set_flags_compare(anded, value, state);
+                    state.x = anded.wrapping_sub(value);
+                },
+                Mnemonic::ANE => {
+                    state.a = (state.a | UNSTABLE_MAGIC) & state.x & value;
+                    set_flags_nz(state.a, state);
+                },
+                _ => {
+                    let result: u8 = (state.a | UNSTABLE_MAGIC) & value;
+                    state.a = result;
+                    state.x = result;
+                    set_flags_nz(result, state);
+                },
+            };
+        },
+        Mnemonic::JAM => {
+            // the CPU locks up with PC still pointing at the JAM opcode
+            cpu.jam();
+            let state: &mut CPUState = cpu.state_mut();
+            state.pc = state.pc.wrapping_sub(1);
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    cycles_undocumented(mnemonic, addressing_mode, cpu.crossed_page_boundary())
+}
@@ -0,0 +1,174 @@
+use crate::instruction_evaluation::evaluate_arithmetic::{add_with_carry_word, subtract_with_borrow_word};
+use crate::instruction_evaluation::evaluate_compare::set_flags_compare_word;
+use crate::instruction_evaluation::evaluate_shift::cycles_read_modify_write;
+use crate::instruction_evaluation::evaluate_transfer::cycles_transfer;
+use crate::instruction_evaluation::flags::{set_flag, set_flags_nz_word};
+use crate::instruction::{AddressingMode, Mnemonic};
+use crate::cpu::CPUState;
+use crate::Bus;
+use crate::Observer;
+use crate::CpuError;
+use crate::CPU;
+
+/// 16-bit read-modify-write takes two cycles more than the 8-bit form, except on C
+const fn cycles_read_modify_write_word(addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    match (addressing_mode, cycles_read_modify_write(addressing_mode)) {
+        (AddressingMode::Accumulator, cycles) => cycles,
+        (_, Ok(cycles)) => Ok(cycles + 2),
+        (_, Err(error)) => Err(error),
+    }
+}
+
+/// 16-bit loads, stores and accumulator operations take a cycle more than their 8-bit forms
+const fn cycles_transfer_word(addressing_mode: AddressingMode, page_crossed: bool) -> Result<u8, CpuError> {
+    match cycles_transfer(addressing_mode, page_crossed) {
+        Ok(cycles) => Ok(cycles + 1),
+        Err(error) => Err(error),
+    }
+}
+
+pub(crate) const fn cycles_wide(mnemonic: Mnemonic, addressing_mode: AddressingMode, page_crossed: bool) -> Result<u8, CpuError> {
+    match mnemonic {
+        Mnemonic::LDA | Mnemonic::LDX | Mnemonic::LDY
+        | Mnemonic::ADC | Mnemonic::SBC | Mnemonic::AND | Mnemonic::ORA | Mnemonic::EOR | Mnemonic::BIT
+        | Mnemonic::CMP | Mnemonic::CPX | Mnemonic::CPY => cycles_transfer_word(addressing_mode, page_crossed),
+        Mnemonic::STA | Mnemonic::STX | Mnemonic::STY | Mnemonic::STZ => cycles_transfer_word(addressing_mode, true),
+        Mnemonic::TRB | Mnemonic::TSB
+        | Mnemonic::ASL | Mnemonic::LSR | Mnemonic::ROL | Mnemonic::ROR | Mnemonic::INC | Mnemonic::DEC
+            => cycles_read_modify_write_word(addressing_mode),
+        Mnemonic::PHA | Mnemonic::PHX | Mnemonic::PHY => Ok(4),
+        Mnemonic::PLA | Mnemonic::PLX | Mnemonic::PLY => Ok(5),
+        Mnemonic::INX | Mnemonic::INY | Mnemonic::DEX | Mnemonic::DEY
+        | Mnemonic::TAX | Mnemonic::TAY | Mnemonic::TSX | Mnemonic::TXA | Mnemonic::TYA | Mnemonic::TXY | Mnemonic::TYX
+        | Mnemonic::TXS => Ok(2),
+        _ => Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    }
+}
+
+/// 16-bit forms of the accumulator and index register instructions, executed in 65C816 native
+/// mode while M or X select 16-bit registers. memory operands are little endian words and take
+/// a cycle more than the 8-bit forms. immediate operands are two bytes long.
+pub fn evaluate_wide<B: Bus, O: Observer>(cpu: &mut CPU<B, O>, mnemonic: Mnemonic, addressing_mode: AddressingMode) -> Result<u8, CpuError> {
+    let state: CPUState = *cpu.get_state();
+
+    match mnemonic {
+        Mnemonic::LDA | Mnemonic::LDX | Mnemonic::LDY => {
+            let value: u16 = cpu.resolve_address_and_get_word(addressing_mode)?;
+            let new_state: &mut CPUState = cpu.state_mut();
+            match mnemonic {
+                Mnemonic::LDA => new_state.set_accumulator(value),
+                Mnemonic::LDX => new_state.set_index_x(value),
+                _ => new_state.set_index_y(value),
+            };
+            set_flags_nz_word(value, new_state);
+        },
+        Mnemonic::STA | Mnemonic::STX | Mnemonic::STY | Mnemonic::STZ => {
+            let value: u16 = match mnemonic {
+                Mnemonic::STA => state.accumulator(),
+                Mnemonic::STX => state.index_x(),
+                Mnemonic::STY => state.index_y(),
+                _ => 0,
+            };
+            cpu.resolve_address_and_set_word(addressing_mode, value)?;
+        },
+        Mnemonic::ADC | Mnemonic::SBC | Mnemonic::AND | Mnemonic::ORA | Mnemonic::EOR | Mnemonic::BIT
+        | Mnemonic::CMP | Mnemonic::CPX | Mnemonic::CPY => {
+            let value: u16 = cpu.resolve_address_and_get_word(addressing_mode)?;
+            let new_state: &mut CPUState = cpu.state_mut();
+            let a: u16 = new_state.accumulator();
+            match mnemonic {
+                Mnemonic::ADC => add_with_carry_word(new_state, value),
+                Mnemonic::SBC => subtract_with_borrow_word(new_state, value),
+                Mnemonic::AND => {new_state.set_accumulator(a & value); set_flags_nz_word(a & value, new_state)},
+                Mnemonic::ORA => {new_state.set_accumulator(a | value); set_flags_nz_word(a | value, new_state)},
+                Mnemonic::EOR => {new_state.set_accumulator(a ^ value); set_flags_nz_word(a ^ value, new_state)},
+                Mnemonic::BIT => {
+                    set_flag(new_state, flag!(zero), a & value == 0);
+                    // the immediate form has no memory operand to take N and V from
+                    if addressing_mode != AddressingMode::Immediate {
+                        set_flag(new_state, flag!(negative), value & 0x8000 != 0);
+                        set_flag(new_state, flag!(overflow), value & 0x4000 != 0);
+                    }
+                },
+                Mnemonic::CMP => set_flags_compare_word(a, value, new_state),
+                Mnemonic::CPX => set_flags_compare_word(state.index_x(), value, new_state),
+                _ => set_flags_compare_word(state.index_y(), value, new_state),
+            };
+        },
+        Mnemonic::TRB | Mnemonic::TSB => {
+            let a: u16 = state.accumulator();
+            let mut tested: u16 = 0;
+            cpu.resolve_address_and_modify_word(addressing_mode, |value| {
+                tested = a & value;
+                match mnemonic {
+                    Mnemonic::TRB => value & !a,
+                    _ => value | a,
+                }
+            })?;
+            set_flag(cpu.state_mut(), flag!(zero), tested == 0);
+        },
+        Mnemonic::ASL | Mnemonic::LSR | Mnemonic::ROL | Mnemonic::ROR | Mnemonic::INC | Mnemonic::DEC => {
+            let carry_in: u16 = (state.sr & flag!(carry)) as u16;
+            let mut carry_out: Option<bool> = None;
+            let result: u16 = cpu.resolve_address_and_modify_word(addressing_mode, |value| {
+                match mnemonic {
+                    Mnemonic::ASL => {carry_out = Some(value & 0x8000 != 0); value << 1},
+                    Mnemonic::LSR => {carry_out = Some(value & 0x0001 != 0); value >> 1},
+                    Mnemonic::ROL => {carry_out = Some(value & 0x8000 != 0); (value << 1) | carry_in},
+                    Mnemonic::ROR => {carry_out = Some(value & 0x0001 != 0); (value >> 1) | (carry_in << 15)},
+                    Mnemonic::INC => value.wrapping_add(1),
+                    _ => value.wrapping_sub(1),
+                }
+            })?;
+            let new_state: &mut CPUState = cpu.state_mut();
+            if let Some(carry_out) = carry_out {
+                set_flag(new_state, flag!(carry), carry_out);
+            }
+            set_flags_nz_word(result, new_state);
+        },
+        Mnemonic::PHA | Mnemonic::PHX | Mnemonic::PHY => {
+            let value: u16 = match mnemonic {
+                Mnemonic::PHA => state.accumulator(),
+                Mnemonic::PHX => state.index_x(),
+                _ => state.index_y(),
+            };
+            cpu.push_word(value);
+        },
+        Mnemonic::PLA | Mnemonic::PLX | Mnemonic::PLY => {
+            let value: u16 = cpu.pull_word();
+            let new_state: &mut CPUState = cpu.state_mut();
+            match mnemonic {
+                Mnemonic::PLA => new_state.set_accumulator(value),
+                Mnemonic::PLX => new_state.set_index_x(value),
+                _ => new_state.set_index_y(value),
+            };
+            set_flags_nz_word(value, new_state);
+        },
+        Mnemonic::INX | Mnemonic::INY | Mnemonic::DEX | Mnemonic::DEY
+        | Mnemonic::TAX | Mnemonic::TAY | Mnemonic::TSX | Mnemonic::TXA | Mnemonic::TYA | Mnemonic::TXY | Mnemonic::TYX => {
+            let new_state: &mut CPUState = cpu.state_mut();
+            let value: u16 = match mnemonic {
+                Mnemonic::INX => state.index_x().wrapping_add(1),
+                Mnemonic::INY => state.index_y().wrapping_add(1),
+                Mnemonic::DEX => state.index_x().wrapping_sub(1),
+                Mnemonic::DEY => state.index_y().wrapping_sub(1),
+                Mnemonic::TAX | Mnemonic::TAY => state.accumulator(),
+                Mnemonic::TSX => state.stack_pointer(),
+                Mnemonic::TXA | Mnemonic::TXY => state.index_x(),
+                _ => state.index_y(),
+            };
+            match mnemonic {
+                Mnemonic::INX | Mnemonic::DEX | Mnemonic::TAX | Mnemonic::TSX | Mnemonic::TYX => new_state.set_index_x(value),
+                Mnemonic::INY | Mnemonic::DEY | Mnemonic::TAY | Mnemonic::TXY => new_state.set_index_y(value),
+                _ => new_state.set_accumulator(value),
+            };
+            set_flags_nz_word(value, new_state);
+        },
+        Mnemonic::TXS => {
+            cpu.state_mut().set_stack_pointer(state.index_x());
+        },
+        _ => return Err(CpuError::UnsupportedMnemonic(mnemonic)),
+    };
+
+    cycles_wide(mnemonic, addressing_mode, cpu.crossed_page_boundary())
+}
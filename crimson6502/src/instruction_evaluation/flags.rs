@@ -0,0 +1,22 @@
+use crate::cpu::CPUState;
+
+/// sets or clears the given flag depending on condition
+pub fn set_flag(state: &mut CPUState, flag: u8, condition: bool) {
+    if condition {
+        state.sr |= flag;
+    } else {
+        state.sr &= !flag;
+    }
+}
+
+/// sets N and Z from value, as done by every instruction producing a register or memory result
+pub fn set_flags_nz(value: u8, state: &mut CPUState) {
+    set_flag(state, flag!(zero), value == 0);
+    set_flag(state, flag!(negative), value & 0x80 != 0);
+}
+
+/// sets N and Z from a 16-bit result of the 65C816
+pub fn set_flags_nz_word(value: u16, state: &mut CPUState) {
+    set_flag(state, flag!(zero), value == 0);
+    set_flag(state, flag!(negative), value & 0x8000 != 0);
+}
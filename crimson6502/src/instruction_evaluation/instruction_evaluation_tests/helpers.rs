@@ -1,4 +1,4 @@
-use crate::{instruction::AddressingMode, CPUState, CPU};
+use crate::{instruction::AddressingMode, util::bytes_to_word, CPUState, CPU};
 
 /// input: AddressingMode::{ ZeroPageX, AbsoluteX, IndirectX, ZeroPageY, AbsoluteY, IndirectY, ZeroPage, Absolute }
 pub fn maybe_apply_index_value(addressing_mode: AddressingMode, state: &mut CPUState, index_value: u8) {
@@ -32,13 +32,18 @@ pub fn compute_effective_address(addressing_mode: AddressingMode, index_value: u
         AbsoluteY
             => addr.wrapping_add(index_value as u16),
         IndirectX
-            => cpu.read_word_zp((addr as u8).wrapping_add(index_value)),
+            => peek_word_zp((addr as u8).wrapping_add(index_value), cpu),
         IndirectY
-            => cpu.read_word_zp(addr as u8).wrapping_add(index_value as u16),
+            => peek_word_zp(addr as u8, cpu).wrapping_add(index_value as u16),
         ZeroPage |
         Absolute
             => addr,
         _
             => panic!("invalid addressing mode for {}: {:?}", stringify!(compute_effective_address), addressing_mode),
     }
+}
+
+/// reads a zero-page pointer without side effects, wrapping around zero page
+fn peek_word_zp(addr: u8, cpu: &CPU) -> u16 {
+    bytes_to_word(cpu.peek_byte(addr as u16), cpu.peek_byte(addr.wrapping_add(1) as u16))
 }
\ No newline at end of file
@@ -1,3 +1,28 @@
 mod helpers;
 
-mod test_evaluate_load;
\ No newline at end of file
+mod test_evaluate_load;
+mod test_evaluate_arithmetic;
+mod test_evaluate_compare;
+mod test_evaluate_branch;
+mod test_evaluate_stack;
+mod test_evaluate_jump;
+mod test_evaluate_interrupt;
+mod test_evaluate_logical;
+mod test_evaluate_shift;
+mod test_evaluate_increment;
+mod test_evaluate_undocumented;
+mod test_evaluate_variant;
+mod test_evaluate_bit_manipulation;
+mod test_evaluate_native;
+mod test_evaluate_bus;
+mod test_evaluate_memory_map;
+mod test_evaluate_bank_switching;
+mod test_evaluate_error;
+mod test_evaluate_tick;
+mod test_evaluate_dummy_access;
+mod test_evaluate_snapshot;
+mod test_evaluate_history;
+mod test_evaluate_observer;
+mod test_evaluate_run_until;
+mod test_evaluate_decode;
+mod test_evaluate_block_cache;
\ No newline at end of file
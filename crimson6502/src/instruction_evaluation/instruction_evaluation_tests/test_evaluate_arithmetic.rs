@@ -0,0 +1,80 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+pub fn test_arithmetic_immediate_helper(mnemonic: Mnemonic, a: u8, value: u8, sr: u8, expected_a: u8, expected_sr: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = a;
+    cpu_state.sr = sr;
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.a = expected_a;
+    expected_state.sr = expected_sr;
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_arithmetic(&mut cpu, mnemonic, AddressingMode::Immediate).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_adc_binary() {
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x12, 0x34, 0, 0x46, 0);
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x12, 0x34, flag!(carry), 0x47, 0);
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0xFF, 0x01, 0, 0x00, flag!(carry) | flag!(zero));
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x50, 0x50, 0, 0xA0, flag!(overflow) | flag!(negative));
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0xD0, 0x90, 0, 0x60, flag!(overflow) | flag!(carry));
+}
+
+#[test]
+pub fn test_sbc_binary() {
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x50, 0x20, flag!(carry), 0x30, flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x50, 0x20, 0, 0x2F, flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x50, 0xF0, flag!(carry), 0x60, 0);
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x50, 0xB0, flag!(carry), 0xA0, flag!(overflow) | flag!(negative));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0xD0, 0x70, flag!(carry), 0x60, flag!(overflow) | flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x42, 0x42, flag!(carry), 0x00, flag!(carry) | flag!(zero));
+}
+
+#[test]
+pub fn test_adc_decimal() {
+    let d: u8 = flag!(decimal);
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x12, 0x34, d, 0x46, d);
+    // intermediate result 0xA5 sets N and V even though the BCD result is 0x05
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x58, 0x46, d | flag!(carry), 0x05, d | flag!(carry) | flag!(overflow) | flag!(negative));
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x09, 0x01, d, 0x10, d);
+    // NMOS quirk: Z follows the binary sum (0x9A), N follows the half-adjusted intermediate (0xA0)
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x99, 0x01, d, 0x00, d | flag!(carry) | flag!(negative));
+    // NMOS quirk: V is computed before the high nibble is adjusted
+    test_arithmetic_immediate_helper(Mnemonic::ADC, 0x79, 0x00, d | flag!(carry), 0x80, d | flag!(overflow) | flag!(negative));
+}
+
+#[test]
+pub fn test_sbc_decimal() {
+    let d: u8 = flag!(decimal);
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x46, 0x12, d | flag!(carry), 0x34, d | flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x40, 0x13, d | flag!(carry), 0x27, d | flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x32, 0x02, d, 0x29, d | flag!(carry));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x12, 0x21, d | flag!(carry), 0x91, d | flag!(negative));
+    test_arithmetic_immediate_helper(Mnemonic::SBC, 0x00, 0x00, d | flag!(carry), 0x00, d | flag!(carry) | flag!(zero));
+}
+
+#[test]
+pub fn test_adc_absolute_indexed_page_cross() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = 0x01;
+    cpu_state.x = 0x01;
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0, 0xFF);
+    cpu.write_byte(1, 0x20);
+    cpu.write_byte(0x2100, 0x02);
+
+    let cycles: u8 = evaluate_arithmetic(&mut cpu, Mnemonic::ADC, AddressingMode::AbsoluteX).unwrap();
+    assert_eq!(cpu.get_state().a, 0x03);
+    assert_eq!(cycles, 5);
+}
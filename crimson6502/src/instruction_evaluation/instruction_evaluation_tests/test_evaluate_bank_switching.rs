@@ -0,0 +1,119 @@
+use crate::Bus;
+use crate::CPUState;
+use crate::CPU;
+use crate::memory_map::*;
+
+/// RAM at $0000-$3FFF with a bank latch at $3FFF, eight 16K ROM banks at $8000-$BFFF
+/// selected by $3FFE, and fixed ROM at $C000-$FFFF
+fn banked_description() -> MemoryMapDescription {
+    MemoryMapDescription::new()
+        .ram(0x0000, 0x3FFF)
+        .banked_ram(0x4000, 0x7FFF, 4, 0x3FFF)
+        .banked_rom(0x8000, 0xBFFF, 8, 0x3FFE)
+        .rom(0xC000, 0xFFFF)
+        .rom_writes(RomWrites::Report)
+}
+
+/// ROM image with every byte of bank n set to n
+fn banked_image() -> Vec<u8> {
+    (0..8u8).flat_map(|bank| vec![bank; 0x4000]).collect()
+}
+
+#[test]
+pub fn test_bank_select_register() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&banked_description()).unwrap();
+    memory_map.load_bank(0x8000, 0, &banked_image()).unwrap();
+
+    assert_eq!(memory_map.read(0x8000), 0);
+    memory_map.write(0x3FFE, 5);
+    assert_eq!(memory_map.read(0x8000), 5);
+    assert_eq!(memory_map.read(0xBFFF), 5);
+    // only the low bits select a bank
+    memory_map.write(0x3FFE, 11);
+    assert_eq!(memory_map.read(0x9000), 3);
+    // the latch is not RAM
+    assert_eq!(memory_map.peek(0x3FFE), 0);
+}
+
+#[test]
+pub fn test_banked_ram() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&banked_description()).unwrap();
+    memory_map.write(0x4000, 0x11);
+    memory_map.write(0x3FFF, 2);
+    memory_map.write(0x4000, 0x22);
+
+    assert_eq!(memory_map.read(0x4000), 0x22);
+    memory_map.write(0x3FFF, 0);
+    assert_eq!(memory_map.read(0x4000), 0x11);
+}
+
+#[test]
+pub fn test_banked_rom_is_read_only() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&banked_description()).unwrap();
+    memory_map.load_bank(0x8000, 1, &[0xAA]).unwrap();
+    memory_map.select_bank(0x8000, 1).unwrap();
+    memory_map.write(0x8000, 0x55);
+
+    assert_eq!(memory_map.read(0x8000), 0xAA);
+    assert_eq!(memory_map.take_rom_writes(), vec![RomWrite { addr: 0x8000, value: 0x55 }]);
+}
+
+#[test]
+pub fn test_mapped_banks() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&banked_description()).unwrap();
+    memory_map.write(0x3FFE, 6);
+
+    assert_eq!(memory_map.mapped_banks(), vec![
+        BankMapping { start: 0x4000, end: 0x7FFF, bank: 0, banks: 4, select: 0x3FFF },
+        BankMapping { start: 0x8000, end: 0xBFFF, bank: 6, banks: 8, select: 0x3FFE },
+    ]);
+    assert_eq!(memory_map.select_bank(0xC000, 1), Err(MemoryMapError::NotBanked(0xC000)));
+}
+
+#[test]
+pub fn test_cpu_switches_banks() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&banked_description()).unwrap();
+    memory_map.load_bank(0x8000, 0, &banked_image()).unwrap();
+    // LDA #$04, STA $3FFE, LDX $8000
+    memory_map.load(0xC000, &[0xA9, 0x04, 0x8D, 0xFE, 0x3F, 0xAE, 0x00, 0x80]);
+    let mut cpu_state: CPUState = CPUState::new();
+    cpu_state.pc = 0xC000;
+    let mut cpu: CPU<MemoryMap> = CPU::new(cpu_state, memory_map);
+    for _ in 0..3 {
+        cpu.run().unwrap();
+    }
+
+    assert_eq!(cpu.get_state().x, 4);
+    assert_eq!(cpu.bus().mapped_banks()[1].bank, 4);
+}
+
+#[test]
+pub fn test_banked_description_from_toml() {
+    let source: &str = r#"
+        [[region]]
+        kind = "banked_rom"
+        start = 0x8000
+        end = 0xBFFF
+        banks = 8
+        select = 0x3FFE
+    "#;
+    let description: MemoryMapDescription = MemoryMapDescription::from_toml(source).unwrap();
+    assert_eq!(description, MemoryMapDescription::new().banked_rom(0x8000, 0xBFFF, 8, 0x3FFE));
+
+    let no_banks: MemoryMapDescription = MemoryMapDescription::new().banked_ram(0x4000, 0x7FFF, 0, 0x3FFF);
+    assert!(matches!(MemoryMap::new(&no_banks), Err(MemoryMapError::InvalidBanks(_))));
+}
+
+#[test]
+pub fn test_bank_count_is_bounded() {
+    let too_many: MemoryMapDescription = MemoryMapDescription::new().banked_rom(0x0000, 0xFF_FFFF, u32::MAX, 0x0000);
+    assert!(matches!(MemoryMap::new(&too_many), Err(MemoryMapError::InvalidBanks(_))));
+
+    let one_too_many: MemoryMapDescription = MemoryMapDescription::new().banked_ram(0x4000, 0x7FFF, 257, 0x3FFF);
+    assert!(matches!(MemoryMap::new(&one_too_many), Err(MemoryMapError::InvalidBanks(_))));
+
+    let most: MemoryMapDescription = MemoryMapDescription::new().banked_ram(0x4000, 0x7FFF, 256, 0x3FFF);
+    let mut map: MemoryMap = MemoryMap::new(&most).unwrap();
+    map.select_bank(0x4000, 255).unwrap();
+    assert_eq!(map.mapped_banks()[0].bank, 255);
+}
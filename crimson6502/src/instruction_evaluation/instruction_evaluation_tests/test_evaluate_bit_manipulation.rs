@@ -0,0 +1,127 @@
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::CpuError;
+use crate::RunState;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+fn wdc_cpu(state: CPUState) -> CPU {
+    CPU::with_variant(state, Memory::new(), CPUVariant::Wdc65C02)
+}
+
+pub fn test_reset_set_bit_helper(opcode: u8, value: u8, expected_value: u8) {
+    let mut cpu: CPU = wdc_cpu(CPUState::new());
+    cpu.write_byte(0, 0x40);
+    cpu.write_byte(0x40, value);
+
+    let instruction: Instruction = Instruction::from_byte(opcode, CPUVariant::Wdc65C02).unwrap();
+    let mut expected_state = CPUState::new();
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_bit_manipulation(&mut cpu, opcode, instruction.mnemonic, instruction.addressing_mode).unwrap(), 5);
+    assert_eq!(*cpu.get_state(), expected_state);
+    assert_eq!(cpu.read_byte(0x40), expected_value);
+}
+
+pub fn test_branch_on_bit_helper(opcode: u8, pc: u16, value: u8, offset: u8, expected_pc: u16, expected_cycles: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = pc;
+    let mut cpu: CPU = wdc_cpu(cpu_state);
+    cpu.write_byte(pc, 0x40);
+    cpu.write_byte(pc + 1, offset);
+    cpu.write_byte(0x40, value);
+
+    let instruction: Instruction = Instruction::from_byte(opcode, CPUVariant::Wdc65C02).unwrap();
+    let mut expected_state = cpu_state;
+    expected_state.pc = expected_pc;
+
+    assert_eq!(evaluate_bit_manipulation(&mut cpu, opcode, instruction.mnemonic, instruction.addressing_mode).unwrap(), expected_cycles);
+    assert_eq!(*cpu.get_state(), expected_state);
+}
+
+#[test]
+pub fn test_reset_set_bit() {
+    // RMB0, RMB7, SMB3, SMB7
+    test_reset_set_bit_helper(0x07, 0xFF, 0xFE);
+    test_reset_set_bit_helper(0x77, 0xFF, 0x7F);
+    test_reset_set_bit_helper(0xB7, 0x00, 0x08);
+    test_reset_set_bit_helper(0xF7, 0x01, 0x81);
+}
+
+#[test]
+pub fn test_branch_on_bit() {
+    // BBR0 taken, BBR0 not taken
+    test_branch_on_bit_helper(0x0F, 0x0200, 0xFE, 0x10, 0x0212, 6);
+    test_branch_on_bit_helper(0x0F, 0x0200, 0x01, 0x10, 0x0202, 5);
+    // BBS7 taken backwards across a page, BBS5 not taken
+    test_branch_on_bit_helper(0xFF, 0x0200, 0x80, 0xF0, 0x01F2, 7);
+    test_branch_on_bit_helper(0xDF, 0x0200, 0xDF, 0xF0, 0x0202, 5);
+}
+
+#[test]
+pub fn test_bit_opcodes_only_on_wdc() {
+    assert!(matches!(Instruction::from_byte(0x87, CPUVariant::Wdc65C02).unwrap().mnemonic, Mnemonic::SMB));
+    assert!(matches!(Instruction::from_byte(0x87, CPUVariant::Cmos65C02).unwrap().mnemonic, Mnemonic::NOP));
+    assert!(matches!(Instruction::from_byte(0xCB, CPUVariant::Wdc65C02).unwrap().mnemonic, Mnemonic::WAI));
+    assert!(matches!(Instruction::from_byte(0xCB, CPUVariant::Nmos6502).unwrap().mnemonic, Mnemonic::SBX));
+}
+
+#[test]
+pub fn test_wai_halts_until_interrupt() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = wdc_cpu(cpu_state);
+    cpu.write_byte(0x0200, 0xCB);
+    cpu.write_byte(0x0201, 0xE8);
+    cpu.write_byte(crate::IRQ_VECTOR, 0x00);
+    cpu.write_byte(crate::IRQ_VECTOR + 1, 0x30);
+
+    cpu.run().unwrap();
+    assert_eq!(cpu.run_state(), RunState::Waiting);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x0201);
+
+    cpu.set_irq(true);
+    cpu.run().unwrap();
+    assert_eq!(cpu.run_state(), RunState::Running);
+    assert_eq!(cpu.get_state().pc, 0x3000);
+}
+
+#[test]
+pub fn test_wai_with_masked_irq_resumes_without_servicing() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.sr = flag!(interrupt);
+    let mut cpu: CPU = wdc_cpu(cpu_state);
+    cpu.write_byte(0x0200, 0xCB);
+    cpu.write_byte(0x0201, 0xE8);
+
+    cpu.run().unwrap();
+    cpu.set_irq(true);
+    cpu.run().unwrap();
+    assert_eq!(cpu.run_state(), RunState::Running);
+    assert_eq!(cpu.get_state().pc, 0x0202);
+    assert_eq!(cpu.get_state().x, 1);
+}
+
+#[test]
+pub fn test_stp_halts_until_reset() {
+    let mut cpu: CPU = wdc_cpu(CPUState::new());
+    cpu.write_byte(0, 0xDB);
+    assert_eq!(cpu.run(), Err(CpuError::Halted { run_state: RunState::Stopped, addr: 1 }));
+    assert_eq!(cpu.run_state(), RunState::Stopped);
+
+    cpu.set_nmi(true);
+    assert_eq!(cpu.run(), Err(CpuError::Halted { run_state: RunState::Stopped, addr: 1 }));
+    assert_eq!(cpu.run_state(), RunState::Stopped);
+    assert_eq!(cpu.get_state().pc, 1);
+
+    cpu.write_byte(crate::RESET_VECTOR, 0x00);
+    cpu.write_byte(crate::RESET_VECTOR + 1, 0x80);
+    cpu.init_state();
+    assert_eq!(cpu.run_state(), RunState::Running);
+    assert_eq!(cpu.get_state().pc, 0x8000);
+}
@@ -0,0 +1,181 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::Bus;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::StopReason;
+use crate::CPU;
+
+/// instructions run per random program
+const STEPS: usize = 50_000;
+
+fn random_cpu(rng: &mut StdRng, variant: CPUVariant) -> CPU {
+    let mut contents: Vec<u8> = vec![0; 0x10000];
+    rng.fill(&mut contents[..]);
+    let mut memory: Memory = Memory::new();
+    memory.set_contents(&contents);
+
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = rng.random();
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, memory, variant);
+    cpu.set_bus_log(true);
+    cpu
+}
+
+/// runs random bytes as code, which also overwrites itself all the time, on the plain interpreter
+/// and with the block cache. every instruction must do the same.
+#[test]
+pub fn test_block_cache_matches_interpreter_on_random_programs() {
+    for (seed, variant) in [
+        (1, CPUVariant::Nmos6502),
+        (2, CPUVariant::Nmos6502),
+        (3, CPUVariant::Wdc65C02),
+        (4, CPUVariant::Cmos65C02),
+        (5, CPUVariant::Wdc65C816),
+        (6, CPUVariant::Wdc65C816),
+    ] {
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let mut interpreter: CPU = random_cpu(&mut rng, variant);
+        let mut cached: CPU = random_cpu(&mut StdRng::seed_from_u64(seed), variant);
+        cached.set_block_cache(true);
+        let mut cache_used: bool = false;
+
+        for step in 0..STEPS {
+            // interrupts now and then, which also wake up WAI
+            let irq: bool = rng.random_ratio(1, 200);
+            let nmi: bool = rng.random_ratio(1, 500);
+            for cpu in [&mut interpreter, &mut cached] {
+                cpu.set_irq(irq);
+                cpu.set_nmi(nmi);
+            }
+
+            let result = interpreter.run();
+            assert_eq!(cached.run(), result, "seed {} step {}", seed, step);
+            assert_eq!(cached.get_state(), interpreter.get_state(), "seed {} step {}", seed, step);
+            assert_eq!(cached.get_stats(), interpreter.get_stats(), "seed {} step {}", seed, step);
+            assert_eq!(cached.take_bus_log(), interpreter.take_bus_log(), "seed {} step {}", seed, step);
+            cache_used |= cached.cached_blocks() > 1;
+
+            // JAM and STP halt until reset, start over at the reset vector
+            if result.is_err() {
+                interpreter.init_state();
+                cached.init_state();
+            }
+        }
+        assert_eq!(cached.bus().save_contents(), interpreter.bus().save_contents(), "seed {}", seed);
+        assert!(cache_used, "seed {}", seed);
+    }
+}
+
+/// run_until runs whole cached blocks. budgets and breakpoints must stop it on the same
+/// instruction as the plain interpreter.
+#[test]
+pub fn test_block_cache_run_until_matches_interpreter() {
+    for (seed, variant) in [
+        (7, CPUVariant::Nmos6502),
+        (8, CPUVariant::Wdc65C02),
+        (9, CPUVariant::Wdc65C816),
+    ] {
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let mut interpreter: CPU = random_cpu(&mut rng, variant);
+        let mut cached: CPU = random_cpu(&mut StdRng::seed_from_u64(seed), variant);
+        cached.set_block_cache(true);
+        for _ in 0..16 {
+            let addr: u32 = rng.random::<u16>() as u32;
+            interpreter.add_breakpoint(addr);
+            cached.add_breakpoint(addr);
+        }
+
+        for step in 0..STEPS / 10 {
+            let budget: usize = rng.random_range(1..200);
+            let result = interpreter.run_until(budget);
+            assert_eq!(cached.run_until(budget), result, "seed {} step {}", seed, step);
+            assert_eq!(cached.get_state(), interpreter.get_state(), "seed {} step {}", seed, step);
+            assert_eq!(cached.get_stats(), interpreter.get_stats(), "seed {} step {}", seed, step);
+            assert_eq!(cached.take_bus_log(), interpreter.take_bus_log(), "seed {} step {}", seed, step);
+
+            // start over at the reset vector after halts, waits and illegal opcodes
+            if matches!(result, Ok(StopReason::Halted { .. } | StopReason::Waiting { .. } | StopReason::IllegalOpcode { .. })) {
+                interpreter.init_state();
+                cached.init_state();
+            }
+        }
+        assert_eq!(cached.bus().save_contents(), interpreter.bus().save_contents(), "seed {}", seed);
+    }
+}
+
+/// $0200: INX, JMP $0200
+fn looping_cpu() -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in [0xE8, 0x4C, 0x00, 0x02].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu.set_block_cache(true);
+    cpu
+}
+
+#[test]
+pub fn test_block_cache_invalidated_by_writes() {
+    let mut cpu: CPU = looping_cpu();
+    for _ in 0..4 {
+        cpu.run().unwrap();
+    }
+    assert_eq!(cpu.get_state().x, 2);
+    assert_eq!(cpu.cached_blocks(), 1);
+
+    // a write elsewhere keeps the block, a write to the operand of JMP drops it
+    cpu.write_byte(0x0300, 0xC8);
+    assert_eq!(cpu.cached_blocks(), 1);
+    cpu.write_byte(0x0203, 0x02);
+    assert_eq!(cpu.cached_blocks(), 0);
+
+    // INX becomes INY
+    cpu.run().unwrap();
+    cpu.write_byte(0x0200, 0xC8);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().x, 3);
+    assert_eq!(cpu.get_state().y, 1);
+}
+
+#[test]
+pub fn test_block_cache_notices_code_changed_by_the_bus() {
+    let mut cpu: CPU = looping_cpu();
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    // writes to the bus itself bypass the invalidation, the fetched opcode still differs: DEX
+    cpu.bus_mut().write(0x0200, 0xCA);
+    assert_eq!(cpu.cached_blocks(), 1);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().x, 0);
+}
+
+/// $0200: LDA #$C8, STA $0206, NOP, INX. the store turns the INX of the same block into INY
+#[test]
+pub fn test_block_cache_self_modifying_block() {
+    let program: [u8; 7] = [0xA9, 0xC8, 0x8D, 0x06, 0x02, 0xEA, 0xE8];
+    for tick in [false, true] {
+        let mut cpu_state = CPUState::new();
+        cpu_state.pc = 0x0200;
+        let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+        for (i, byte) in program.iter().enumerate() {
+            cpu.write_byte(0x0200 + i as u16, *byte);
+        }
+        cpu.set_block_cache(true);
+
+        while cpu.get_stats().instructions() < 4 {
+            match tick {
+                true => cpu.tick().unwrap(),
+                false => cpu.run().unwrap(),
+            }
+        }
+        assert_eq!(cpu.get_state().x, 0);
+        assert_eq!(cpu.get_state().y, 1);
+        assert_eq!(cpu.get_state().pc, 0x0207);
+    }
+}
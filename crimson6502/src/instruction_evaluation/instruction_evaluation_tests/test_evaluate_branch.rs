@@ -0,0 +1,47 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+pub fn test_branch_helper(mnemonic: Mnemonic, sr: u8, pc: u16, offset: u8, expected_pc: u16, expected_cycles: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.sr = sr;
+    cpu_state.pc = pc;
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(pc, offset);
+
+    let mut expected_state = cpu_state;
+    expected_state.pc = expected_pc;
+
+    assert_eq!(evaluate_branch(&mut cpu, mnemonic, AddressingMode::Relative).unwrap(), expected_cycles);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_branch_conditions() {
+    let branches: [(Mnemonic, Mnemonic, u8); 4] = [
+        (Mnemonic::BCS, Mnemonic::BCC, flag!(carry)),
+        (Mnemonic::BEQ, Mnemonic::BNE, flag!(zero)),
+        (Mnemonic::BMI, Mnemonic::BPL, flag!(negative)),
+        (Mnemonic::BVS, Mnemonic::BVC, flag!(overflow)),
+    ];
+    for (if_set, if_clear, flag) in branches.iter() {
+        // offset is relative to the instruction following the branch, pc points at the operand
+        test_branch_helper(*if_set, *flag, 0x0201, 0x10, 0x0212, 3);
+        test_branch_helper(*if_set, 0, 0x0201, 0x10, 0x0202, 2);
+        test_branch_helper(*if_clear, 0, 0x0201, 0x10, 0x0212, 3);
+        test_branch_helper(*if_clear, *flag, 0x0201, 0x10, 0x0202, 2);
+    }
+}
+
+#[test]
+pub fn test_branch_page_cross() {
+    // backwards across a page
+    test_branch_helper(Mnemonic::BNE, 0, 0x0201, 0xF0, 0x01F2, 4);
+    // forwards across a page
+    test_branch_helper(Mnemonic::BNE, 0, 0x02F0, 0x7F, 0x0370, 4);
+    // not taken never pays for the page cross
+    test_branch_helper(Mnemonic::BEQ, 0, 0x02F0, 0x7F, 0x02F1, 2);
+}
@@ -0,0 +1,82 @@
+use crate::Bus;
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+
+/// RAM with a counter at $D000 that increments on every read and an output port at $D001
+#[derive(Debug, PartialEq)]
+struct CounterBus {
+    memory: Memory,
+    counter: u8,
+    output: Vec<u8>,
+}
+
+impl Bus for CounterBus {
+    fn read(&mut self, addr: u32) -> u8 {
+        match addr {
+            0xD000 => {
+                self.counter = self.counter.wrapping_add(1);
+                self.counter
+            },
+            _ => self.memory.read(addr),
+        }
+    }
+
+    fn write(&mut self, addr: u32, value: u8) {
+        match addr {
+            0xD001 => self.output.push(value),
+            _ => self.memory.write(addr, value),
+        }
+    }
+
+    fn peek(&self, addr: u32) -> u8 {
+        match addr {
+            0xD000 => self.counter,
+            _ => self.memory.peek(addr),
+        }
+    }
+}
+
+fn counter_cpu(program: &[u8]) -> CPU<CounterBus> {
+    let bus: CounterBus = CounterBus { memory: Memory::new(), counter: 0, output: Vec::new() };
+    let mut cpu: CPU<CounterBus> = CPU::new(CPUState::new(), bus);
+    for (i, byte) in program.iter().enumerate() {
+        cpu.write_byte(i as u16, *byte);
+    }
+    cpu
+}
+
+#[test]
+pub fn test_memory_mapped_read() {
+    // LDA $D000, LDX $D000
+    let mut cpu: CPU<CounterBus> = counter_cpu(&[0xAD, 0x00, 0xD0, 0xAE, 0x00, 0xD0]);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().a, 1);
+    assert_eq!(cpu.get_state().x, 2);
+    assert_eq!(cpu.bus().counter, 2);
+}
+
+#[test]
+pub fn test_memory_mapped_write() {
+    // LDA #$48, STA $D001, LDA #$69, STA $D001
+    let mut cpu: CPU<CounterBus> = counter_cpu(&[0xA9, 0x48, 0x8D, 0x01, 0xD0, 0xA9, 0x69, 0x8D, 0x01, 0xD0]);
+    for _ in 0..4 {
+        cpu.run().unwrap();
+    }
+
+    assert_eq!(cpu.bus().output, vec![0x48, 0x69]);
+    assert_eq!(cpu.bus().memory.peek(0xD001), 0x00);
+}
+
+#[test]
+pub fn test_peek_has_no_side_effects() {
+    let mut cpu: CPU<CounterBus> = counter_cpu(&[]);
+    cpu.bus_mut().counter = 0x41;
+
+    assert_eq!(cpu.peek_byte(0xD000), 0x41);
+    assert_eq!(cpu.peek_byte(0xD000), 0x41);
+    assert_eq!(cpu.read_byte(0xD000), 0x42);
+    assert_eq!(cpu.peek_byte(0xD000), 0x42);
+}
@@ -0,0 +1,58 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+pub fn test_compare_immediate_helper(mnemonic: Mnemonic, register: u8, value: u8, expected_sr: u8) {
+    let mut cpu_state = CPUState::new();
+    match mnemonic {
+        Mnemonic::CMP => cpu_state.a = register,
+        Mnemonic::CPX => cpu_state.x = register,
+        Mnemonic::CPY => cpu_state.y = register,
+        _ => panic!("not a compare mnemonic: {:?}", mnemonic),
+    };
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.sr = expected_sr;
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_compare(&mut cpu, mnemonic, AddressingMode::Immediate).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_compare_relations() {
+    let mnemonics: [Mnemonic; 3] = [Mnemonic::CMP, Mnemonic::CPX, Mnemonic::CPY];
+    for mnemonic in mnemonics.iter() {
+        // register < operand
+        test_compare_immediate_helper(*mnemonic, 0x10, 0x20, flag!(negative));
+        test_compare_immediate_helper(*mnemonic, 0x10, 0xA0, 0);
+        // register = operand
+        test_compare_immediate_helper(*mnemonic, 0x42, 0x42, flag!(zero) | flag!(carry));
+        // register > operand
+        test_compare_immediate_helper(*mnemonic, 0x20, 0x10, flag!(carry));
+        test_compare_immediate_helper(*mnemonic, 0xFF, 0x01, flag!(carry) | flag!(negative));
+    }
+}
+
+#[test]
+pub fn test_compare_indirect_indexed_page_cross() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = 0x33;
+    cpu_state.y = 0x10;
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0, 0x40);
+    cpu.write_byte(0x40, 0xF8);
+    cpu.write_byte(0x41, 0x12);
+    cpu.write_byte(0x1308, 0x33);
+
+    let cycles: u8 = evaluate_compare(&mut cpu, Mnemonic::CMP, AddressingMode::IndirectY).unwrap();
+    assert_eq!(cpu.get_state().a, 0x33);
+    assert_eq!(cpu.get_state().sr, flag!(zero) | flag!(carry));
+    assert_eq!(cycles, 6);
+}
@@ -0,0 +1,88 @@
+use crate::decode::Handler;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::DecodedOpcode;
+use crate::Instruction;
+use crate::Memory;
+use crate::Mnemonic;
+use crate::CPU;
+
+const VARIANTS: [CPUVariant; 5] = [
+    CPUVariant::Nmos6502,
+    CPUVariant::Ricoh2A03,
+    CPUVariant::Cmos65C02,
+    CPUVariant::Wdc65C02,
+    CPUVariant::Wdc65C816,
+];
+
+#[test]
+pub fn test_decode_table_matches_decoder() {
+    for variant in VARIANTS {
+        for byte in 0..=0xFF {
+            let decoded: Option<&DecodedOpcode> = DecodedOpcode::from_byte(byte, variant);
+            assert_eq!(decoded.map(|decoded| decoded.instruction), Instruction::decode(byte, variant));
+            if let Some(decoded) = decoded {
+                assert_eq!(decoded.instruction.opcode, byte);
+                assert_eq!(decoded.length, decoded.instruction.addressing_mode.instruction_length());
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_decode_table_base_cycles() {
+    let base_cycles = |byte: u8, variant: CPUVariant| DecodedOpcode::from_byte(byte, variant).unwrap().base_cycles;
+    // LDA #, STA abs,X, INC abs,X, JSR, BRK, BNE
+    assert_eq!(base_cycles(0xA9, CPUVariant::Nmos6502), 2);
+    assert_eq!(base_cycles(0x9D, CPUVariant::Nmos6502), 5);
+    assert_eq!(base_cycles(0xFE, CPUVariant::Nmos6502), 7);
+    assert_eq!(base_cycles(0x20, CPUVariant::Nmos6502), 6);
+    assert_eq!(base_cycles(0x00, CPUVariant::Nmos6502), 7);
+    assert_eq!(base_cycles(0xD0, CPUVariant::Nmos6502), 2);
+    // ASL abs,X only takes the extra cycle on the 65C02 when crossing a page, the NOPs in column 3 take one cycle
+    assert_eq!(base_cycles(0x1E, CPUVariant::Nmos6502), 7);
+    assert_eq!(base_cycles(0x1E, CPUVariant::Wdc65C02), 6);
+    assert_eq!(base_cycles(0x03, CPUVariant::Wdc65C02), 1);
+    assert_eq!(base_cycles(0x5C, CPUVariant::Wdc65C02), 8);
+    // JSL, MVN
+    assert_eq!(base_cycles(0x22, CPUVariant::Wdc65C816), 8);
+    assert_eq!(base_cycles(0x54, CPUVariant::Wdc65C816), 7);
+}
+
+#[test]
+pub fn test_decode_table_length() {
+    let length = |byte: u8| DecodedOpcode::from_byte(byte, CPUVariant::Nmos6502).unwrap().length;
+    // LDA ($zp,X) and LDA ($zp),Y take a zero page operand
+    assert_eq!(length(0xA1), 1);
+    assert_eq!(length(0xB1), 1);
+    // LDA #, LDA abs, TAX
+    assert_eq!(length(0xA9), 1);
+    assert_eq!(length(0xAD), 2);
+    assert_eq!(length(0xAA), 0);
+}
+
+/// every opcode without a branch or halt takes its base cycles when executed on zeroed memory
+#[test]
+pub fn test_decode_table_base_cycles_match_execution() {
+    for variant in VARIANTS {
+        for byte in 0..=0xFF {
+            let Some(decoded) = DecodedOpcode::from_byte(byte, variant) else {
+                continue;
+            };
+            let branches: bool = matches!(decoded.handler, Handler::Branch | Handler::Halt)
+                || matches!(decoded.instruction.mnemonic, Mnemonic::BBR | Mnemonic::BBS | Mnemonic::JAM | Mnemonic::MVN | Mnemonic::MVP);
+            if branches {
+                continue;
+            }
+
+            let mut cpu_state = CPUState::new();
+            cpu_state.pc = 0x0200;
+            cpu_state.s = 0xFF;
+            let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), variant);
+            cpu.set_undocumented_opcodes(true);
+            cpu.write_byte(0x0200, byte);
+            cpu.run().unwrap();
+            assert_eq!(cpu.get_stats().total_cycles(), decoded.base_cycles as usize, "opcode {:02X} on {:?}", byte, variant);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::BusAccess;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Instruction;
+use crate::Memory;
+use crate::Mnemonic;
+use crate::CPU;
+
+fn dummy_access_cpu(program: &[u8], x: u8, y: u8) -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0300;
+    cpu_state.s = 0xFD;
+    cpu_state.x = x;
+    cpu_state.y = y;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in program.iter().enumerate() {
+        cpu.write_byte(0x0300 + i as u16, *byte);
+    }
+    cpu.set_bus_log(true);
+    cpu
+}
+
+#[test]
+pub fn test_one_access_per_cycle() {
+    for opcode in 0..=0xFF {
+        let Some(instruction) = Instruction::from_byte(opcode, CPUVariant::Nmos6502) else {
+            continue;
+        };
+        if let Mnemonic::JAM = instruction.mnemonic {
+            continue;
+        }
+        for index in [0x01, 0x80] {
+            let mut cpu: CPU = dummy_access_cpu(&[opcode, 0xC0, 0x12], index, index);
+            cpu.run().unwrap();
+
+            assert_eq!(cpu.bus_log().len(), cpu.get_stats().total_cycles(), "opcode ${:02X}, index ${:02X}: {:?}", opcode, index, cpu.bus_log());
+        }
+    }
+}
+
+#[test]
+pub fn test_indexed_store_dummy_read() {
+    // STA $12C0,X
+    let mut cpu: CPU = dummy_access_cpu(&[0x9D, 0xC0, 0x12], 0x01, 0);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0300, 0x9D),
+        BusAccess::read(0x0301, 0xC0),
+        BusAccess::read(0x0302, 0x12),
+        BusAccess::read(0x12C1, 0x00),
+        BusAccess::write(0x12C1, 0x00),
+    ]);
+}
+
+#[test]
+pub fn test_implied_and_stack_dummy_reads() {
+    // PLA, RTS
+    let mut cpu: CPU = dummy_access_cpu(&[0x68, 0x60], 0, 0);
+    cpu.write_byte(0x01FE, 0x42);
+    cpu.write_byte(0x01FF, 0x0F);
+    cpu.write_byte(0x0100, 0x04);
+    cpu.take_bus_log();
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().pc, 0x0410);
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0300, 0x68),
+        BusAccess::read(0x0301, 0x60),
+        BusAccess::read(0x01FD, 0x00),
+        BusAccess::read(0x01FE, 0x42),
+        BusAccess::read(0x0301, 0x60),
+        BusAccess::read(0x0302, 0x00),
+        BusAccess::read(0x01FE, 0x42),
+        BusAccess::read(0x01FF, 0x0F),
+        BusAccess::read(0x0100, 0x04),
+        BusAccess::read(0x040F, 0x00),
+    ]);
+}
+
+#[test]
+pub fn test_jsr_access_order() {
+    // JSR $1234
+    let mut cpu: CPU = dummy_access_cpu(&[0x20, 0x34, 0x12], 0, 0);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().pc, 0x1234);
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0300, 0x20),
+        BusAccess::read(0x0301, 0x34),
+        BusAccess::read(0x01FD, 0x00),
+        BusAccess::write(0x01FD, 0x03),
+        BusAccess::write(0x01FC, 0x02),
+        BusAccess::read(0x0302, 0x12),
+    ]);
+}
+
+#[test]
+pub fn test_taken_branch_dummy_reads() {
+    // BNE -$10
+    let mut cpu: CPU = dummy_access_cpu(&[0xD0, 0xF0], 0, 0);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().pc, 0x02F2);
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0300, 0xD0),
+        BusAccess::read(0x0301, 0xF0),
+        BusAccess::read(0x0302, 0x00),
+        BusAccess::read(0x03F2, 0x00),
+    ]);
+}
+
+#[test]
+pub fn test_dummy_accesses_disabled() {
+    // INC $12C0,X, TAX
+    let mut cpu: CPU = dummy_access_cpu(&[0xFE, 0xC0, 0x12, 0xAA], 0x01, 0);
+    cpu.set_dummy_accesses(false);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0300, 0xFE),
+        BusAccess::read(0x0301, 0xC0),
+        BusAccess::read(0x0302, 0x12),
+        BusAccess::read(0x12C1, 0x00),
+        BusAccess::write(0x12C1, 0x01),
+        BusAccess::read(0x0303, 0xAA),
+    ]);
+    assert_eq!(cpu.get_stats().total_cycles(), 9);
+}
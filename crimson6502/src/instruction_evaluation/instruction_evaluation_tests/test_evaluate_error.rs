@@ -0,0 +1,90 @@
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::CpuError;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+use crate::memory_map::*;
+
+#[test]
+pub fn test_illegal_opcode() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.set_undocumented_opcodes(false);
+    // NOP, SLO $10
+    cpu.write_byte(0x0200, 0xEA);
+    cpu.write_byte(0x0201, 0x07);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.run(), Err(CpuError::IllegalOpcode { opcode: 0x07, addr: 0x0201 }));
+    assert_eq!(format!("{}", cpu.run().unwrap_err()), "illegal opcode $07 at $000201");
+}
+
+#[test]
+pub fn test_unsupported_mnemonic_and_addressing_mode() {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+
+    assert_eq!(evaluate_load(&mut cpu, Mnemonic::STA, AddressingMode::Immediate), Err(CpuError::UnsupportedMnemonic(Mnemonic::STA)));
+    assert_eq!(cpu.resolve_address(AddressingMode::Implied), Err(CpuError::UnsupportedAddressingMode(AddressingMode::Implied)));
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JMP, AddressingMode::Immediate), Err(CpuError::UnsupportedAddressingMode(AddressingMode::Immediate)));
+}
+
+#[test]
+pub fn test_breakpoint() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    // INX, INX, INX
+    for addr in 0x0200..0x0203 {
+        cpu.write_byte(addr, 0xE8);
+    }
+    cpu.add_breakpoint(0x0201);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.run(), Err(CpuError::Breakpoint { addr: 0x0201 }));
+    assert_eq!(cpu.get_state().x, 1);
+    // running again resumes past the breakpoint
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().x, 2);
+
+    assert!(cpu.remove_breakpoint(0x0201));
+    assert!(!cpu.remove_breakpoint(0x0201));
+}
+
+#[test]
+pub fn test_breakpoint_uses_program_bank() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x8000;
+    cpu_state.pbr = 0x01;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::with_banks(2), CPUVariant::Wdc65C816);
+    cpu.write_byte_long(0x018000, 0xEA);
+    cpu.add_breakpoint(0x8000);
+    cpu.run().unwrap();
+
+    cpu.add_breakpoint(0x018001);
+    assert_eq!(cpu.run(), Err(CpuError::Breakpoint { addr: 0x018001 }));
+}
+
+#[test]
+pub fn test_bus_fault() {
+    let description: MemoryMapDescription = MemoryMapDescription::new()
+        .ram(0x0000, 0x7FFF)
+        .rom(0x8000, 0xFFFF)
+        .rom_writes(RomWrites::Fault);
+    let mut memory_map: MemoryMap = MemoryMap::new(&description).unwrap();
+    // LDA #$01, STA $C000, LDA #$02
+    memory_map.load(0x8000, &[0xA9, 0x01, 0x8D, 0x00, 0xC0, 0xA9, 0x02]);
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x8000;
+    let mut cpu: CPU<MemoryMap> = CPU::new(cpu_state, memory_map);
+    cpu.run().unwrap();
+
+    // the faulting instruction completes, its write is dropped
+    assert_eq!(cpu.run(), Err(CpuError::BusFault { addr: 0xC000 }));
+    assert_eq!(cpu.get_state().pc, 0x8005);
+    assert_eq!(cpu.peek_byte(0xC000), 0x00);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().a, 0x02);
+}
@@ -0,0 +1,168 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::CpuError;
+use crate::memory_map::*;
+
+/// $0200: LDX #$03, loop: INC $10,X, PHA, DEX, BNE loop, BRK
+fn history_cpu(capacity: usize) -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in [0xA2, 0x03, 0xF6, 0x10, 0x48, 0xCA, 0xD0, 0xFA, 0x00].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu.set_history_capacity(capacity);
+    cpu
+}
+
+#[test]
+pub fn test_step_back() {
+    let mut cpu: CPU = history_cpu(100);
+    let mut states: Vec<CPUState> = Vec::new();
+    let mut memory: Vec<Memory> = Vec::new();
+    for _ in 0..13 {
+        states.push(*cpu.get_state());
+        memory.push(Memory::new());
+        for addr in 0..0x0300 {
+            memory.last_mut().unwrap().write_byte(addr, cpu.peek_byte(addr));
+        }
+        cpu.run().unwrap();
+    }
+
+    assert_eq!(cpu.history_len(), 13);
+    for step in (0..13).rev() {
+        assert_eq!(cpu.step_back(1), 1);
+        assert_eq!(*cpu.get_state(), states[step]);
+        for addr in 0..0x0300 {
+            assert_eq!(cpu.peek_byte(addr), memory[step].read_byte(addr));
+        }
+    }
+    assert_eq!(cpu.step_back(1), 0);
+    assert_eq!(cpu.get_stats().instructions(), 0);
+}
+
+#[test]
+pub fn test_step_back_until() {
+    let mut cpu: CPU = history_cpu(100);
+    for _ in 0..12 {
+        cpu.run().unwrap();
+    }
+
+    // back to the start of the third, then the second loop iteration
+    assert_eq!(cpu.step_back_until(0x0202), Some(3));
+    assert_eq!(cpu.get_state().x, 1);
+    assert_eq!(cpu.step_back_until(0x0202), Some(4));
+    assert_eq!(cpu.get_state().x, 2);
+    assert_eq!(cpu.peek_byte(0x12), 0);
+    assert_eq!(cpu.peek_byte(0x13), 1);
+    assert_eq!(cpu.step_back_until(0x0300), None);
+    assert_eq!(cpu.get_state().pc, 0x0200);
+}
+
+#[test]
+pub fn test_history_capacity() {
+    let mut cpu: CPU = history_cpu(4);
+    for _ in 0..10 {
+        cpu.run().unwrap();
+    }
+
+    assert_eq!(cpu.history_len(), 4);
+    assert_eq!(cpu.step_back(10), 4);
+    assert_eq!(cpu.get_stats().instructions(), 6);
+    cpu.set_history_capacity(0);
+    cpu.run().unwrap();
+    assert_eq!(cpu.history_len(), 0);
+    assert_eq!(cpu.step_back(1), 0);
+}
+
+#[test]
+pub fn test_step_back_abandons_ticked_instruction() {
+    let mut cpu: CPU = history_cpu(100);
+    cpu.run().unwrap();
+    // INC $13: read, dummy write and the final write of the incremented value
+    for _ in 0..6 {
+        cpu.tick().unwrap();
+    }
+    assert_eq!(cpu.peek_byte(0x13), 1);
+    assert_eq!(cpu.history_len(), 2);
+
+    // PHA
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+    assert!(cpu.instruction_in_progress());
+    assert_eq!(cpu.step_back(0), 0);
+    assert!(!cpu.instruction_in_progress());
+    assert_eq!(cpu.get_state().pc, 0x0204);
+    assert_eq!(cpu.step_back(1), 1);
+    assert_eq!(cpu.get_state().pc, 0x0202);
+    assert_eq!(cpu.peek_byte(0x13), 0);
+}
+
+#[test]
+pub fn test_interrupt_is_a_step() {
+    let mut cpu: CPU = history_cpu(100);
+    cpu.run().unwrap();
+    cpu.set_nmi(true);
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.history_len(), 2);
+    cpu.step_back(1);
+    assert!(cpu.nmi_pending());
+    assert_eq!(cpu.get_state().s, 0xFF);
+    assert_eq!(cpu.peek_byte(0x01FF), 0x00);
+}
+
+#[test]
+pub fn test_step_back_restores_bank() {
+    let description: MemoryMapDescription = MemoryMapDescription::new()
+        .ram(0x0000, 0x7FFF)
+        .banked_ram(0x8000, 0xBFFF, 4, 0x7FFF);
+    let mut memory_map: MemoryMap = MemoryMap::new(&description).unwrap();
+    memory_map.load_bank(0x8000, 0, &[0x11]).unwrap();
+    memory_map.load_bank(0x8000, 2, &[0x22]).unwrap();
+    // the RAM behind the select register, which writing it back would latch
+    memory_map.load(0x7FFF, &[0x03]);
+    // LDA #$02, STA $7FFF, LDA #$AA, STA $8000
+    memory_map.load(0x0200, &[0xA9, 0x02, 0x8D, 0xFF, 0x7F, 0xA9, 0xAA, 0x8D, 0x00, 0x80]);
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU<MemoryMap> = CPU::new(cpu_state, memory_map);
+    cpu.set_history_capacity(100);
+    for _ in 0..4 {
+        cpu.run().unwrap();
+    }
+    assert_eq!(cpu.peek_byte(0x8000), 0xAA);
+
+    // the byte goes back to bank 2, then the bank select is undone
+    assert_eq!(cpu.step_back(2), 2);
+    assert_eq!(cpu.bus().mapped_banks()[0].bank, 2);
+    assert_eq!(cpu.peek_byte(0x8000), 0x22);
+    assert_eq!(cpu.step_back(1), 1);
+    assert_eq!(cpu.bus().mapped_banks()[0].bank, 0);
+    assert_eq!(cpu.peek_byte(0x8000), 0x11);
+    assert_eq!(cpu.peek_byte(0x7FFF), 0x03);
+}
+
+#[test]
+pub fn test_step_back_over_rom_write() {
+    let description: MemoryMapDescription = MemoryMapDescription::new()
+        .ram(0x0000, 0x7FFF)
+        .rom(0x8000, 0xFFFF)
+        .rom_writes(RomWrites::Fault);
+    let mut memory_map: MemoryMap = MemoryMap::new(&description).unwrap();
+    // LDA #$01, STA $C000
+    memory_map.load(0x8000, &[0xA9, 0x01, 0x8D, 0x00, 0xC0]);
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x8000;
+    let mut cpu: CPU<MemoryMap> = CPU::new(cpu_state, memory_map);
+    cpu.set_history_capacity(100);
+    cpu.run().unwrap();
+    assert_eq!(cpu.run(), Err(CpuError::BusFault { addr: 0xC000 }));
+
+    // undoing the dropped write does not fault again
+    assert_eq!(cpu.step_back(2), 2);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x8002);
+}
\ No newline at end of file
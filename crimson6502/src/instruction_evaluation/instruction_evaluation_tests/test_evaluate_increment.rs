@@ -0,0 +1,121 @@
+use rand::Rng;
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+fn increment_flags_helper(value: u8) -> u8 {
+    let mut flags = 0;
+    if value > 127 {
+        flags |= flag!(negative);
+    }
+    if value == 0 {
+        flags |= flag!(zero);
+    }
+    flags
+}
+
+fn expected_increment_value(mnemonic: Mnemonic, value: u8) -> u8 {
+    match mnemonic {
+        Mnemonic::INC | Mnemonic::INX | Mnemonic::INY => value.wrapping_add(1),
+        Mnemonic::DEC | Mnemonic::DEX | Mnemonic::DEY => value.wrapping_sub(1),
+        _ => panic!("not an increment mnemonic: {:?}", mnemonic),
+    }
+}
+
+pub fn test_increment_register_helper(mnemonic: Mnemonic, value: u8) {
+    let mut cpu_state = CPUState::new();
+    match mnemonic {
+        Mnemonic::INX | Mnemonic::DEX => cpu_state.x = value,
+        Mnemonic::INY | Mnemonic::DEY => cpu_state.y = value,
+        _ => panic!("not a register increment mnemonic: {:?}", mnemonic),
+    };
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    let expected_value: u8 = expected_increment_value(mnemonic, value);
+    let mut expected_state = cpu_state;
+    match mnemonic {
+        Mnemonic::INX | Mnemonic::DEX => expected_state.x = expected_value,
+        _ => expected_state.y = expected_value,
+    };
+    expected_state.sr = increment_flags_helper(expected_value);
+
+    assert_eq!(evaluate_increment(&mut cpu, mnemonic, AddressingMode::Implied).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+pub fn test_increment_memory_helper(mnemonic: Mnemonic, value: u8, addr: u16, addressing_mode: AddressingMode) {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    cpu.write_byte(0, addr as u8);
+    cpu.write_byte(1, (addr >> 8) as u8);
+    cpu.write_byte(addr, value);
+
+    let expected_value: u8 = expected_increment_value(mnemonic, value);
+    let mut expected_state = CPUState::new();
+    expected_state.sr = increment_flags_helper(expected_value);
+    expected_state.pc = addressing_mode.instruction_length() as u16;
+
+    assert_eq!(evaluate_increment(&mut cpu, mnemonic, addressing_mode).unwrap(), if addressing_mode == AddressingMode::ZeroPage {5} else {6});
+    assert_eq!(*cpu.get_state(), expected_state);
+    assert_eq!(cpu.read_byte(addr), expected_value);
+}
+
+pub fn test_flag_helper(mnemonic: Mnemonic, sr: u8, expected_sr: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.sr = sr;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    let mut expected_state = cpu_state;
+    expected_state.sr = expected_sr;
+
+    assert_eq!(evaluate_flag(&mut cpu, mnemonic, AddressingMode::Implied).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_increment_register() {
+    let mut rng = rand::rng();
+    let mnemonics: [Mnemonic; 4] = [Mnemonic::INX, Mnemonic::INY, Mnemonic::DEX, Mnemonic::DEY];
+    for mnemonic in mnemonics.iter() {
+        test_increment_register_helper(*mnemonic, rng.random::<u8>());
+    }
+}
+
+#[test]
+pub fn test_increment_register_wraparound() {
+    test_increment_register_helper(Mnemonic::INX, 0xFF);
+    test_increment_register_helper(Mnemonic::INY, 0xFF);
+    test_increment_register_helper(Mnemonic::DEX, 0x00);
+    test_increment_register_helper(Mnemonic::DEY, 0x00);
+    test_increment_register_helper(Mnemonic::DEX, 0x01);
+    test_increment_register_helper(Mnemonic::INY, 0x7F);
+}
+
+#[test]
+pub fn test_increment_memory() {
+    let mut rng = rand::rng();
+    test_increment_memory_helper(Mnemonic::INC, rng.random::<u8>(), rng.random_range(0x02..0x100), AddressingMode::ZeroPage);
+    test_increment_memory_helper(Mnemonic::DEC, rng.random::<u8>(), rng.random_range(0x02..0x100), AddressingMode::ZeroPage);
+    test_increment_memory_helper(Mnemonic::INC, rng.random::<u8>(), rng.random_range(0x0200..0xFFFF), AddressingMode::Absolute);
+    test_increment_memory_helper(Mnemonic::DEC, rng.random::<u8>(), rng.random_range(0x0200..0xFFFF), AddressingMode::Absolute);
+}
+
+#[test]
+pub fn test_increment_memory_wraparound() {
+    test_increment_memory_helper(Mnemonic::INC, 0xFF, 0x0040, AddressingMode::ZeroPage);
+    test_increment_memory_helper(Mnemonic::DEC, 0x00, 0x0040, AddressingMode::ZeroPage);
+    test_increment_memory_helper(Mnemonic::INC, 0xFF, 0x1234, AddressingMode::Absolute);
+    test_increment_memory_helper(Mnemonic::DEC, 0x00, 0x1234, AddressingMode::Absolute);
+}
+
+#[test]
+pub fn test_flag_instructions() {
+    test_flag_helper(Mnemonic::SEC, 0, flag!(carry));
+    test_flag_helper(Mnemonic::CLC, 0xFF, !flag!(carry));
+    test_flag_helper(Mnemonic::SEI, 0, flag!(interrupt));
+    test_flag_helper(Mnemonic::CLI, 0xFF, !flag!(interrupt));
+    test_flag_helper(Mnemonic::SED, 0, flag!(decimal));
+    test_flag_helper(Mnemonic::CLD, 0xFF, !flag!(decimal));
+    test_flag_helper(Mnemonic::CLV, 0xFF, !flag!(overflow));
+}
@@ -0,0 +1,122 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+fn write_vector(cpu: &mut CPU, vector: u16, target: u16) {
+    cpu.write_byte(vector, target as u8);
+    cpu.write_byte(vector + 1, (target >> 8) as u8);
+}
+
+#[test]
+pub fn test_reset_vector() {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    write_vector(&mut cpu, crate::RESET_VECTOR, 0xC000);
+    cpu.init_state();
+    assert_eq!(cpu.get_state().pc, 0xC000);
+}
+
+#[test]
+pub fn test_brk_rti() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0201;
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(carry) | flag!(unused);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    write_vector(&mut cpu, crate::IRQ_VECTOR, 0x8000);
+
+    let mut expected_state = cpu_state;
+    expected_state.pc = 0x8000;
+    expected_state.s = 0xFC;
+    expected_state.sr |= flag!(interrupt);
+
+    assert_eq!(evaluate_interrupt(&mut cpu, Mnemonic::BRK, AddressingMode::Implied).unwrap(), 7);
+    assert_eq!(*cpu.get_state(), expected_state);
+    let result_state: CPUState = *cpu.get_state();
+    // return address skips the padding byte, pushed status has B set
+    assert_eq!(cpu.read_byte(0x01FF), 0x02);
+    assert_eq!(cpu.read_byte(0x01FE), 0x02);
+    assert_eq!(cpu.read_byte(0x01FD), flag!(carry) | flag!(unused) | flag!(brk));
+
+    let mut cpu: CPU = CPU::new(result_state, Memory::new());
+    cpu.write_byte(0x01FF, 0x02);
+    cpu.write_byte(0x01FE, 0x02);
+    cpu.write_byte(0x01FD, flag!(carry) | flag!(unused) | flag!(brk));
+
+    let mut expected_state = cpu_state;
+    expected_state.pc = 0x0202;
+
+    assert_eq!(evaluate_interrupt(&mut cpu, Mnemonic::RTI, AddressingMode::Implied).unwrap(), 6);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_irq_masked_by_interrupt_flag() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(interrupt);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    write_vector(&mut cpu, crate::IRQ_VECTOR, 0x8000);
+    cpu.write_byte(0x0200, 0xEA);
+
+    cpu.set_irq(true);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x0201);
+}
+
+#[test]
+pub fn test_irq_level_triggered() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(unused);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    write_vector(&mut cpu, crate::IRQ_VECTOR, 0x8000);
+    // handler returns straight away, RTI restores the clear I flag
+    cpu.write_byte(0x8000, 0x40);
+
+    cpu.set_irq(true);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x8000);
+    assert_eq!(cpu.get_state().sr & flag!(interrupt), flag!(interrupt));
+    // pushed status has B clear
+    assert_eq!(cpu.read_byte(0x01FD), flag!(unused));
+
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x0200);
+    // line still asserted, so the interrupt is taken again
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x8000);
+}
+
+#[test]
+pub fn test_nmi_edge_triggered() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(interrupt);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    write_vector(&mut cpu, crate::NMI_VECTOR, 0x9000);
+    cpu.write_byte(0x9000, 0xEA);
+
+    cpu.set_nmi(true);
+    assert!(cpu.nmi_pending());
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x9000);
+    assert!(!cpu.nmi_pending());
+
+    // holding the line does not retrigger
+    cpu.set_nmi(true);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x9001);
+
+    cpu.set_nmi(false);
+    cpu.set_nmi(true);
+    assert!(cpu.nmi_pending());
+}
@@ -0,0 +1,93 @@
+use rand::Rng;
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+pub fn test_jmp_absolute_helper(target: u16) {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    cpu.write_byte(0, target as u8);
+    cpu.write_byte(1, (target >> 8) as u8);
+
+    let mut expected_state = CPUState::new();
+    expected_state.pc = target;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JMP, AddressingMode::Absolute).unwrap(), 3);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+pub fn test_jmp_indirect_helper(pointer: u16, target: u16) {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    // high byte is fetched without carrying into the pointer's page
+    let pointer_hi: u16 = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+    cpu.write_byte(pointer, target as u8);
+    cpu.write_byte(pointer_hi, (target >> 8) as u8);
+    cpu.write_byte(0, pointer as u8);
+    cpu.write_byte(1, (pointer >> 8) as u8);
+
+    let mut expected_state = CPUState::new();
+    expected_state.pc = target;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JMP, AddressingMode::Indirect).unwrap(), 5);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+pub fn test_jsr_rts_helper(pc: u16, target: u16) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = pc;
+    cpu_state.s = 0xFF;
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(pc, target as u8);
+    cpu.write_byte(pc.wrapping_add(1), (target >> 8) as u8);
+
+    // pushed return address points at the last byte of JSR
+    let return_addr: u16 = pc.wrapping_add(1);
+    let mut expected_state = cpu_state;
+    expected_state.pc = target;
+    expected_state.s = 0xFD;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JSR, AddressingMode::Absolute).unwrap(), 6);
+    assert_eq!(*cpu.get_state(), expected_state);
+    let result_state: CPUState = *cpu.get_state();
+    assert_eq!(cpu.read_byte(0x01FF), (return_addr >> 8) as u8);
+    assert_eq!(cpu.read_byte(0x01FE), return_addr as u8);
+
+    let mut cpu: CPU = CPU::new(result_state, Memory::new());
+    cpu.write_byte(0x01FF, (return_addr >> 8) as u8);
+    cpu.write_byte(0x01FE, return_addr as u8);
+
+    let mut expected_state = result_state;
+    expected_state.pc = pc.wrapping_add(2);
+    expected_state.s = 0xFF;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::RTS, AddressingMode::Implied).unwrap(), 6);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_jmp_absolute() {
+    let mut rng = rand::rng();
+    test_jmp_absolute_helper(rng.random::<u16>());
+}
+
+#[test]
+pub fn test_jmp_indirect() {
+    let mut rng = rand::rng();
+    test_jmp_indirect_helper(rng.random_range(0x0200..0xFF00) & 0xFFFE, rng.random::<u16>());
+}
+
+#[test]
+pub fn test_jmp_indirect_page_wrap_bug() {
+    test_jmp_indirect_helper(0x30FF, 0x1234);
+    test_jmp_indirect_helper(0xFFFF, 0xABCD);
+}
+
+#[test]
+pub fn test_jsr_rts() {
+    let mut rng = rand::rng();
+    test_jsr_rts_helper(rng.random_range(0x0200..0xFF00), rng.random::<u16>());
+    // return address crossing a page
+    test_jsr_rts_helper(0x02FE, 0x8000);
+}
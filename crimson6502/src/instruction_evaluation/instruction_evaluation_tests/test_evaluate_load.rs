@@ -33,14 +33,8 @@ pub fn test_load_immediate_helper(mnemonic: Mnemonic, expected_value: u8) {
 
     expected_state.sr = transfer_flags_helper(expected_value);
 
-    assert_eq!(
-        evaluate_load(&mut cpu, mnemonic, AddressingMode::Immediate),
-        InstructionResult {
-            state: expected_state,
-            cycles: 2,
-            instruction_length: 1,
-        }
-    )
+    assert_eq!(evaluate_load(&mut cpu, mnemonic, AddressingMode::Immediate).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
 }
 
 pub fn test_load_zeropage_helper(mnemonic: Mnemonic, expected_value: u8, addr: u8, addressing_mode: AddressingMode, index_value: u8) {
@@ -50,9 +44,9 @@ pub fn test_load_zeropage_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
     let effective_addr: u16 = compute_effective_address(addressing_mode, index_value, addr as u16, &cpu);
     cpu.write_byte(0, addr);
-    cpu.write_byte(effective_addr as u16, expected_value);
+    cpu.write_byte(effective_addr, expected_value);
 
-    let mut expected_state = cpu_state.clone();
+    let mut expected_state = cpu_state;
     match mnemonic {
         Mnemonic::LDA => expected_state.a = expected_value,
         Mnemonic::LDX => expected_state.x = expected_value,
@@ -62,14 +56,8 @@ pub fn test_load_zeropage_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     expected_state.sr = transfer_flags_helper(expected_value);
     expected_state.pc = 1;
 
-    assert_eq!(
-        evaluate_load(&mut cpu, mnemonic, addressing_mode),
-        InstructionResult {
-            state: expected_state,
-            cycles: if addressing_mode == AddressingMode::ZeroPage {3} else {4},
-            instruction_length: 1,
-        }
-    )
+    assert_eq!(evaluate_load(&mut cpu, mnemonic, addressing_mode).unwrap(), if addressing_mode == AddressingMode::ZeroPage {3} else {4});
+    assert_eq!(*cpu.get_state(), expected_state)
 }
 
 pub fn test_load_absolute_helper(mnemonic: Mnemonic, expected_value: u8, addr: u16, addressing_mode: AddressingMode, index_value: u8) {
@@ -77,11 +65,11 @@ pub fn test_load_absolute_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     maybe_apply_index_value(addressing_mode, &mut cpu_state, index_value);
 
     let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
-    let mut expected_state = cpu_state.clone();
+    let mut expected_state = cpu_state;
     let effective_addr: u16 = compute_effective_address(addressing_mode, index_value, addr, &cpu);
     cpu.write_byte(0, addr as u8);
     cpu.write_byte(1, (addr >> 8) as u8);
-    cpu.write_byte(effective_addr as u16, expected_value);
+    cpu.write_byte(effective_addr, expected_value);
 
     match mnemonic {
         Mnemonic::LDA => expected_state.a = expected_value,
@@ -93,15 +81,12 @@ pub fn test_load_absolute_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     expected_state.pc = 2;
 
     assert_eq!(
-        evaluate_load(&mut cpu, mnemonic, addressing_mode),
-        InstructionResult {
-            state: expected_state,
-            cycles: if addressing_mode == AddressingMode::Absolute {4} else {
-                if (effective_addr &0xFF00) == (addr &0xFF00) {4} else {5}
-            },
-            instruction_length: addressing_mode.instruction_length(),
+        evaluate_load(&mut cpu, mnemonic, addressing_mode).unwrap(),
+        if addressing_mode == AddressingMode::Absolute {4} else {
+            if (effective_addr &0xFF00) == (addr &0xFF00) {4} else {5}
         }
-    )
+    );
+    assert_eq!(*cpu.get_state(), expected_state)
 }
 
 pub fn test_load_indirect_helper(mnemonic: Mnemonic, expected_value: u8, addr: u16, addressing_mode: AddressingMode, index_value: u8) {
@@ -109,11 +94,11 @@ pub fn test_load_indirect_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     maybe_apply_index_value(addressing_mode, &mut cpu_state, index_value);
 
     let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
-    let mut expected_state = cpu_state.clone();
+    let mut expected_state = cpu_state;
     let effective_addr: u16 = compute_effective_address(addressing_mode, index_value, addr, &cpu);
     cpu.write_byte(0, addr as u8);
     cpu.write_byte(1, (addr >> 8) as u8);
-    cpu.write_byte(effective_addr as u16, expected_value);
+    cpu.write_byte(effective_addr, expected_value);
 
     match mnemonic {
         Mnemonic::LDA => expected_state.a = expected_value,
@@ -125,15 +110,12 @@ pub fn test_load_indirect_helper(mnemonic: Mnemonic, expected_value: u8, addr: u
     expected_state.pc = 1;
 
     assert_eq!(
-        evaluate_load(&mut cpu, mnemonic, addressing_mode),
-        InstructionResult {
-            state: expected_state,
-            cycles: if addressing_mode == AddressingMode::IndirectX {6} else {
-                if (effective_addr &0xFF00) == (cpu.read_word_zp(addr as u8) &0xFF00) {5} else {6}
-            },
-            instruction_length: addressing_mode.instruction_length(),
+        evaluate_load(&mut cpu, mnemonic, addressing_mode).unwrap(),
+        if addressing_mode == AddressingMode::IndirectX {6} else {
+            if (effective_addr &0xFF00) == (cpu.read_word_zp(addr as u8) &0xFF00) {5} else {6}
         }
-    )
+    );
+    assert_eq!(*cpu.get_state(), expected_state)
 }
 
 #[test]
@@ -192,4 +174,37 @@ pub fn test_load_indirect_indexed() {
     // LDA
     test_load_indirect_helper(Mnemonic::LDA, rand_num, rand_addr, AddressingMode::IndirectX, rand_num);
     test_load_indirect_helper(Mnemonic::LDA, rand_num, rand_addr, AddressingMode::IndirectY, rand_num);
+}
+
+#[test]
+pub fn test_load_indirect_x_wraps_zero_page() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0x0200, 0xFF);
+    // ($FF,X) takes the high byte of the pointer from $00, not $0100
+    cpu.write_byte(0x00FF, 0x34);
+    cpu.write_byte(0x0000, 0x12);
+    cpu.write_byte(0x0100, 0x56);
+    cpu.write_byte(0x1234, 0x42);
+
+    assert_eq!(evaluate_load(&mut cpu, Mnemonic::LDA, AddressingMode::IndirectX).unwrap(), 6);
+    assert_eq!(cpu.get_state().a, 0x42);
+}
+
+#[test]
+pub fn test_load_indirect_y_wraps_zero_page() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.y = 0x02;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0x0200, 0xFF);
+    // ($FF),Y takes the high byte of the pointer from $00, not $0100
+    cpu.write_byte(0x00FF, 0x34);
+    cpu.write_byte(0x0000, 0x12);
+    cpu.write_byte(0x0100, 0x56);
+    cpu.write_byte(0x1236, 0x42);
+
+    assert_eq!(evaluate_load(&mut cpu, Mnemonic::LDA, AddressingMode::IndirectY).unwrap(), 5);
+    assert_eq!(cpu.get_state().a, 0x42);
 }
\ No newline at end of file
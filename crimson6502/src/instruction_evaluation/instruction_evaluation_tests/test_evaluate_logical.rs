@@ -0,0 +1,92 @@
+use rand::Rng;
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+use super::helpers::*;
+
+fn nz_flags_helper(value: u8) -> u8 {
+    let mut flags = 0;
+    if value > 127 {
+        flags |= flag!(negative);
+    }
+    if value == 0 {
+        flags |= flag!(zero);
+    }
+    flags
+}
+
+pub fn test_logical_zeropage_helper(mnemonic: Mnemonic, a: u8, value: u8, addr: u8, addressing_mode: AddressingMode, index_value: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = a;
+    maybe_apply_index_value(addressing_mode, &mut cpu_state, index_value);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    let effective_addr: u16 = compute_effective_address(addressing_mode, index_value, addr as u16, &cpu);
+    cpu.write_byte(0, addr);
+    cpu.write_byte(effective_addr, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.a = match mnemonic {
+        Mnemonic::AND => a & value,
+        Mnemonic::ORA => a | value,
+        Mnemonic::EOR => a ^ value,
+        _ => panic!("not a logical mnemonic: {:?}", mnemonic),
+    };
+    expected_state.sr = nz_flags_helper(expected_state.a);
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_logical(&mut cpu, mnemonic, addressing_mode).unwrap(), if addressing_mode == AddressingMode::ZeroPage {3} else {4});
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+pub fn test_bit_helper(a: u8, value: u8, addressing_mode: AddressingMode) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = a;
+    // flags outside N, V and Z are untouched
+    cpu_state.sr = flag!(carry) | flag!(unused);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0, 0x80);
+    cpu.write_byte(1, 0x02);
+    let effective_addr: u16 = if addressing_mode == AddressingMode::ZeroPage {0x80} else {0x0280};
+    cpu.write_byte(effective_addr, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.sr |= value & (flag!(negative) | flag!(overflow));
+    if a & value == 0 {
+        expected_state.sr |= flag!(zero);
+    }
+    expected_state.pc = addressing_mode.instruction_length() as u16;
+
+    assert_eq!(evaluate_logical(&mut cpu, Mnemonic::BIT, addressing_mode).unwrap(), if addressing_mode == AddressingMode::ZeroPage {3} else {4});
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_logical_zeropage() {
+    let mut rng = rand::rng();
+    let mnemonics: [Mnemonic; 3] = [Mnemonic::AND, Mnemonic::ORA, Mnemonic::EOR];
+    for mnemonic in mnemonics.iter() {
+        // effective address kept away from the operand byte at 0
+        test_logical_zeropage_helper(*mnemonic, rng.random::<u8>(), rng.random::<u8>(), rng.random_range(0x10..0x80), AddressingMode::ZeroPage, 0);
+        test_logical_zeropage_helper(*mnemonic, rng.random::<u8>(), rng.random::<u8>(), rng.random_range(0x10..0x80), AddressingMode::ZeroPageX, rng.random_range(0x00..0x70));
+    }
+}
+
+#[test]
+pub fn test_logical_flags() {
+    test_logical_zeropage_helper(Mnemonic::AND, 0xF0, 0x0F, 0x10, AddressingMode::ZeroPage, 0);
+    test_logical_zeropage_helper(Mnemonic::ORA, 0x00, 0x80, 0x10, AddressingMode::ZeroPage, 0);
+    test_logical_zeropage_helper(Mnemonic::EOR, 0xFF, 0xFF, 0x10, AddressingMode::ZeroPage, 0);
+}
+
+#[test]
+pub fn test_bit() {
+    let mut rng = rand::rng();
+    test_bit_helper(rng.random::<u8>(), rng.random::<u8>(), AddressingMode::ZeroPage);
+    test_bit_helper(rng.random::<u8>(), rng.random::<u8>(), AddressingMode::Absolute);
+    test_bit_helper(0x01, 0xC0, AddressingMode::ZeroPage);
+    test_bit_helper(0xFF, 0x3F, AddressingMode::Absolute);
+}
@@ -0,0 +1,130 @@
+use crate::Bus;
+use crate::CPUState;
+use crate::CPU;
+use crate::memory_map::*;
+
+/// RAM at $0000-$07FF mirrored up to $1FFF, an I/O page of 8 registers mirrored across
+/// $2000-$3FFF, open bus up to $7FFF and ROM at $8000-$FFFF
+fn machine_description() -> MemoryMapDescription {
+    MemoryMapDescription::new()
+        .ram(0x0000, 0x07FF)
+        .mirror(0x0800, 0x1FFF, 0x0000, 0x0800)
+        .ram(0x2000, 0x2007)
+        .mirror(0x2008, 0x3FFF, 0x2000, 0x0008)
+        .open_bus(0x4000, 0x7FFF)
+        .rom(0x8000, 0xFFFF)
+}
+
+#[test]
+pub fn test_ram_and_mirrors() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&machine_description()).unwrap();
+    memory_map.write(0x0801, 0x42);
+    memory_map.write(0x3FFF, 0x17);
+
+    assert_eq!(memory_map.read(0x0001), 0x42);
+    assert_eq!(memory_map.read(0x1801), 0x42);
+    assert_eq!(memory_map.read(0x2007), 0x17);
+    assert_eq!(memory_map.read(0x200F), 0x17);
+}
+
+#[test]
+pub fn test_open_bus() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&machine_description()).unwrap();
+    memory_map.load(0x8000, &[0xA5]);
+
+    assert_eq!(memory_map.read(0x8000), 0xA5);
+    assert_eq!(memory_map.read(0x4000), 0xA5);
+    // outside every region
+    assert_eq!(memory_map.read(0x01_0000), 0xA5);
+
+    memory_map.write(0x0000, 0x3C);
+    assert_eq!(memory_map.peek(0x5000), 0x3C);
+    assert_eq!(memory_map.read(0x5000), 0x3C);
+}
+
+#[test]
+pub fn test_rom_writes_ignored() {
+    let mut memory_map: MemoryMap = MemoryMap::new(&machine_description()).unwrap();
+    memory_map.load(0xFFFC, &[0x00, 0x80]);
+    memory_map.write(0xFFFC, 0x12);
+
+    assert_eq!(memory_map.read(0xFFFC), 0x00);
+    assert!(memory_map.take_rom_writes().is_empty());
+}
+
+#[test]
+pub fn test_rom_writes_reported() {
+    let description: MemoryMapDescription = machine_description().rom_writes(RomWrites::Report);
+    let mut cpu_state: CPUState = CPUState::new();
+    cpu_state.pc = 0x8000;
+    // LDA #$EA, STA $9000
+    let mut memory_map: MemoryMap = MemoryMap::new(&description).unwrap();
+    memory_map.load(0x8000, &[0xA9, 0xEA, 0x8D, 0x00, 0x90]);
+    let mut cpu: CPU<MemoryMap> = CPU::new(cpu_state, memory_map);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.peek_byte(0x9000), 0x00);
+    assert_eq!(cpu.bus_mut().take_rom_writes(), vec![RomWrite { addr: 0x9000, value: 0xEA }]);
+    assert!(cpu.bus_mut().take_rom_writes().is_empty());
+}
+
+#[test]
+pub fn test_description_from_toml() {
+    let source: &str = r#"
+        rom_writes = "report"
+
+        [[region]]
+        kind = "ram"
+        start = 0x0000
+        end = 0x07FF
+
+        [[region]]
+        kind = "mirror"
+        start = 0x0800
+        end = 0x1FFF
+        target = 0x0000
+        size = 0x0800
+
+        [[region]]
+        kind = "ram"
+        start = 0x2000
+        end = 0x2007
+
+        [[region]]
+        kind = "mirror"
+        start = 0x2008
+        end = 0x3FFF
+        target = 0x2000
+        size = 0x0008
+
+        [[region]]
+        kind = "open_bus"
+        start = 0x4000
+        end = 0x7FFF
+
+        [[region]]
+        kind = "rom"
+        start = 0x8000
+        end = 0xFFFF
+    "#;
+    let description: MemoryMapDescription = MemoryMapDescription::from_toml(source).unwrap();
+    assert_eq!(description, machine_description().rom_writes(RomWrites::Report));
+
+    let round_trip: MemoryMapDescription = MemoryMapDescription::from_toml(&description.to_toml().unwrap()).unwrap();
+    assert_eq!(round_trip, description);
+}
+
+#[test]
+pub fn test_invalid_descriptions() {
+    let overlap: MemoryMapDescription = MemoryMapDescription::new().ram(0x0000, 0x07FF).rom(0x0700, 0xFFFF);
+    assert!(matches!(MemoryMap::new(&overlap), Err(MemoryMapError::Overlap(_, _))));
+
+    let inverted: MemoryMapDescription = MemoryMapDescription::new().ram(0x0800, 0x07FF);
+    assert!(matches!(MemoryMap::new(&inverted), Err(MemoryMapError::InvalidRange(_))));
+
+    let unbacked_mirror: MemoryMapDescription = MemoryMapDescription::new().ram(0x0000, 0x00FF).mirror(0x0800, 0x0FFF, 0x0000, 0x0200);
+    assert!(matches!(MemoryMap::new(&unbacked_mirror), Err(MemoryMapError::InvalidMirror(_))));
+
+    assert!(matches!(MemoryMapDescription::from_toml("[[region]]\nkind = \"flash\""), Err(MemoryMapError::Parse(_))));
+}
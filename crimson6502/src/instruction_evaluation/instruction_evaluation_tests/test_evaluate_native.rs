@@ -0,0 +1,270 @@
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+
+/// 65C816 with two banks of memory, running from $0200 with the stack at $01FF
+fn native_cpu() -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    CPU::with_variant(cpu_state, Memory::with_banks(2), CPUVariant::Wdc65C816)
+}
+
+/// writes program at $0200 and runs the given number of instructions
+fn run_program(cpu: &mut CPU, program: &[u8], instructions: usize) {
+    for (i, byte) in program.iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    for _ in 0..instructions {
+        cpu.run().unwrap();
+    }
+}
+
+// CLC, XCE, REP #$30: native mode with 16-bit registers
+const ENTER_NATIVE_16: [u8; 4] = [0x18, 0xFB, 0xC2, 0x30];
+
+#[test]
+pub fn test_every_65816_opcode_is_documented() {
+    for byte in 0..=255u8 {
+        let instruction: Instruction = Instruction::from_byte(byte, CPUVariant::Wdc65C816).unwrap();
+        assert!(!instruction.undocumented, "opcode {:#04X} decodes to {:?}", byte, instruction.mnemonic);
+    }
+}
+
+#[test]
+pub fn test_exchange_emulation_flag() {
+    let mut cpu: CPU = native_cpu();
+    // CLC, XCE, REP #$30, SEC, XCE
+    run_program(&mut cpu, &[0x18, 0xFB, 0xC2, 0x30, 0x38, 0xFB], 3);
+    assert!(!cpu.get_state().e);
+    assert_eq!(cpu.get_state().sr & flag!(carry), flag!(carry));
+    assert!(cpu.get_state().accumulator_16());
+    assert!(cpu.get_state().index_16());
+
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+    assert!(cpu.get_state().e);
+    assert_eq!(cpu.get_state().sr & flag!(carry), 0);
+    assert!(!cpu.get_state().accumulator_16());
+    assert!(!cpu.get_state().index_16());
+    assert_eq!(cpu.get_state().stack_pointer(), 0x01FF);
+}
+
+#[test]
+pub fn test_16_bit_load_store() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // LDA #$8234, LDX #$1000, STA $3000,X, LDY $3000,X
+    program.extend_from_slice(&[0xA9, 0x34, 0x82, 0xA2, 0x00, 0x10, 0x9D, 0x00, 0x30, 0xBC, 0x00, 0x30]);
+    run_program(&mut cpu, &program, 7);
+
+    assert_eq!(cpu.get_state().accumulator(), 0x8234);
+    assert_eq!(cpu.get_state().index_y(), 0x8234);
+    assert_eq!(cpu.read_byte(0x4000), 0x34);
+    assert_eq!(cpu.read_byte(0x4001), 0x82);
+    assert_eq!(cpu.get_state().sr & flag!(negative), flag!(negative));
+    assert_eq!(cpu.get_state().pc, 0x0210);
+}
+
+#[test]
+pub fn test_16_bit_arithmetic() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // LDA #$7FFF, CLC, ADC #$0001
+    program.extend_from_slice(&[0xA9, 0xFF, 0x7F, 0x18, 0x69, 0x01, 0x00]);
+    // SED, LDA #$1999, CLC, ADC #$0001, SEC, SBC #$0002
+    program.extend_from_slice(&[0xF8, 0xA9, 0x99, 0x19, 0x18, 0x69, 0x01, 0x00, 0x38, 0xE9, 0x02, 0x00]);
+    run_program(&mut cpu, &program, 6);
+    assert_eq!(cpu.get_state().accumulator(), 0x8000);
+    assert_eq!(cpu.get_state().sr & (flag!(overflow) | flag!(negative)), flag!(overflow) | flag!(negative));
+
+    for _ in 0..4 {
+        cpu.run().unwrap();
+    }
+    assert_eq!(cpu.get_state().accumulator(), 0x2000);
+
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().accumulator(), 0x1998);
+    assert_eq!(cpu.get_state().sr & flag!(carry), flag!(carry));
+}
+
+#[test]
+pub fn test_16_bit_read_modify_write() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // ASL $40, INC $42
+    program.extend_from_slice(&[0x06, 0x40, 0xE6, 0x42]);
+    cpu.write_byte(0x40, 0x01);
+    cpu.write_byte(0x41, 0x80);
+    cpu.write_byte(0x42, 0xFF);
+    cpu.write_byte(0x43, 0x00);
+    run_program(&mut cpu, &program, 5);
+
+    assert_eq!(cpu.read_word(0x40), 0x0002);
+    assert_eq!(cpu.get_state().sr & flag!(carry), flag!(carry));
+    assert_eq!(cpu.read_word(0x42), 0x0100);
+}
+
+#[test]
+pub fn test_8_bit_index_clears_high_bytes() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // LDX #$1234, SEP #$10, TXA
+    program.extend_from_slice(&[0xA2, 0x34, 0x12, 0xE2, 0x10, 0x8A]);
+    run_program(&mut cpu, &program, 6);
+
+    assert_eq!(cpu.get_state().index_x(), 0x0034);
+    assert_eq!(cpu.get_state().accumulator(), 0x0034);
+}
+
+#[test]
+pub fn test_long_addressing() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.y = 0x03;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::with_banks(4), CPUVariant::Wdc65C816);
+    // LDA #$5A, LDX #$02, STA $01FFFF,X, STA [$10], LDA [$10],Y, LDA $020001
+    let program: [u8; 18] = [0xA9, 0x5A, 0xA2, 0x02, 0x9F, 0xFF, 0xFF, 0x01, 0x87, 0x10, 0xB7, 0x10, 0xAF, 0x01, 0x00, 0x02, 0xEA, 0xEA];
+    cpu.write_byte(0x10, 0x00);
+    cpu.write_byte(0x11, 0x80);
+    cpu.write_byte(0x12, 0x01);
+    cpu.write_byte_long(0x018003, 0xC3);
+    run_program(&mut cpu, &program, 3);
+
+    // indexing carries into the next bank
+    assert_eq!(cpu.read_byte_long(0x020001), 0x5A);
+
+    cpu.run().unwrap();
+    assert_eq!(cpu.read_byte_long(0x018000), 0x5A);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().a, 0xC3);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().a, 0x5A);
+}
+
+#[test]
+pub fn test_indirect_long_wraps_zero_page() {
+    let mut cpu: CPU = native_cpu();
+    // [$FF] in emulation mode takes the pointer from $FF, $00 and $01, not from page 1
+    cpu.write_byte(0x00FF, 0x34);
+    cpu.write_byte(0x0000, 0x12);
+    cpu.write_byte(0x0001, 0x01);
+    cpu.write_byte(0x0100, 0x56);
+    cpu.write_byte(0x0101, 0x00);
+    cpu.write_byte_long(0x011234, 0x42);
+    // LDA [$FF]
+    run_program(&mut cpu, &[0xA7, 0xFF], 1);
+
+    assert_eq!(cpu.get_state().a, 0x42);
+}
+
+#[test]
+pub fn test_stack_relative() {
+    let mut cpu: CPU = native_cpu();
+    // LDA #$11, PHA, LDA #$22, PHA, LDA $02,S, LDY #$01, LDA ($01,S),Y
+    let program: [u8; 14] = [0xA9, 0x11, 0x48, 0xA9, 0x22, 0x48, 0xA3, 0x02, 0xA0, 0x01, 0xB3, 0x01, 0xEA, 0xEA];
+    cpu.write_byte(0x2212, 0x99);
+    run_program(&mut cpu, &program, 5);
+    assert_eq!(cpu.get_state().a, 0x11);
+
+    // pointer on the stack is $1122
+    cpu.write_byte(0x1123, 0x99);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().a, 0x99);
+}
+
+#[test]
+pub fn test_direct_page_relocation() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // LDA #$1200, TCD, SEP #$20, LDA $34
+    program.extend_from_slice(&[0xA9, 0x00, 0x12, 0x5B, 0xE2, 0x20, 0xA5, 0x34]);
+    cpu.write_byte(0x1234, 0xAB);
+    run_program(&mut cpu, &program, 7);
+
+    assert_eq!(cpu.get_state().d, 0x1200);
+    assert_eq!(cpu.get_state().a, 0xAB);
+}
+
+#[test]
+pub fn test_block_move() {
+    let mut cpu: CPU = native_cpu();
+    let mut program: Vec<u8> = ENTER_NATIVE_16.to_vec();
+    // LDA #$0003, LDX #$1000, LDY #$0000, MVN $01,$00
+    program.extend_from_slice(&[0xA9, 0x03, 0x00, 0xA2, 0x00, 0x10, 0xA0, 0x00, 0x00, 0x54, 0x01, 0x00]);
+    for i in 0..4u16 {
+        cpu.write_byte(0x1000 + i, 0xA0 + i as u8);
+    }
+    run_program(&mut cpu, &program, 10);
+
+    for i in 0..4u32 {
+        assert_eq!(cpu.read_byte_long(0x010000 + i), 0xA0 + i as u8);
+    }
+    assert_eq!(cpu.get_state().accumulator(), 0xFFFF);
+    assert_eq!(cpu.get_state().index_x(), 0x1004);
+    assert_eq!(cpu.get_state().dbr, 0x01);
+    assert_eq!(cpu.get_state().pc, 0x0210);
+}
+
+#[test]
+pub fn test_long_subroutine() {
+    let mut cpu: CPU = native_cpu();
+    // JSL $018000, NOP
+    run_program(&mut cpu, &[0x22, 0x00, 0x80, 0x01, 0xEA], 1);
+    assert_eq!(cpu.get_state().pbr, 0x01);
+    assert_eq!(cpu.get_state().pc, 0x8000);
+
+    // RTL
+    cpu.write_byte_long(0x018000, 0x6B);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pbr, 0x00);
+    assert_eq!(cpu.get_state().pc, 0x0204);
+    assert_eq!(cpu.get_state().s, 0xFF);
+}
+
+#[test]
+pub fn test_native_interrupt() {
+    let mut cpu: CPU = native_cpu();
+    cpu.write_byte(crate::NATIVE_IRQ_VECTOR, 0x00);
+    cpu.write_byte(crate::NATIVE_IRQ_VECTOR + 1, 0x90);
+    cpu.write_byte(0x9000, 0x40);
+    // CLC, XCE, JML $018000
+    run_program(&mut cpu, &[0x18, 0xFB, 0x5C, 0x00, 0x80, 0x01], 3);
+    cpu.write_byte_long(0x018000, 0xEA);
+
+    cpu.set_irq(true);
+    cpu.run().unwrap();
+    cpu.set_irq(false);
+    assert_eq!(cpu.get_state().pbr, 0x00);
+    assert_eq!(cpu.get_state().pc, 0x9000);
+    assert_eq!(cpu.get_state().s, 0xFB);
+
+    // RTI returns to the interrupted bank
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pbr, 0x01);
+    assert_eq!(cpu.get_state().pc, 0x8000);
+}
+
+#[test]
+pub fn test_emulation_mode_matches_65c02() {
+    // LDA #$09, SED, CLC, ADC #$01, CLD, PHA, TSX, INX, TXS, DEX, TXS, PLA, JMP ($10FF)
+    let program: [u8; 19] = [0xA9, 0x09, 0xF8, 0x18, 0x69, 0x01, 0xD8, 0x48, 0xBA, 0xE8, 0x9A, 0xCA, 0x9A, 0x68, 0x6C, 0xFF, 0x10, 0xEA, 0xEA];
+    let mut states: Vec<CPUState> = Vec::new();
+    for variant in [CPUVariant::Cmos65C02, CPUVariant::Wdc65C816] {
+        let mut cpu_state = CPUState::new();
+        cpu_state.pc = 0x0200;
+        cpu_state.s = 0xFF;
+        let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), variant);
+        cpu.write_byte(0x10FF, 0x34);
+        cpu.write_byte(0x1100, 0x12);
+        run_program(&mut cpu, &program, 13);
+        states.push(*cpu.get_state());
+    }
+    assert_eq!(states[0], states[1]);
+    assert_eq!(states[0].pc, 0x1234);
+    assert_eq!(states[0].a, 0x10);
+}
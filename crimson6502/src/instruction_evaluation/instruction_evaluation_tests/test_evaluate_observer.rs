@@ -0,0 +1,124 @@
+use crate::CPUState;
+use crate::Instruction;
+use crate::InstructionResult;
+use crate::InterruptKind;
+use crate::Memory;
+use crate::Mnemonic;
+use crate::Observer;
+use crate::CPU;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Event {
+    Fetch(u32),
+    Executed(Mnemonic, u8),
+    Read(u32, u8),
+    Write(u32, u8),
+    Interrupt(InterruptKind, u16),
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Recorder {
+    events: Vec<Event>,
+}
+
+impl Observer for Recorder {
+    fn before_fetch(&mut self, addr: u32, _state: &CPUState) {
+        self.events.push(Event::Fetch(addr));
+    }
+
+    fn after_execute(&mut self, instruction: &Instruction, result: &InstructionResult) {
+        self.events.push(Event::Executed(instruction.mnemonic, result.cycles));
+    }
+
+    fn on_read(&mut self, addr: u32, value: u8) {
+        self.events.push(Event::Read(addr, value));
+    }
+
+    fn on_write(&mut self, addr: u32, value: u8) {
+        self.events.push(Event::Write(addr, value));
+    }
+
+    fn on_interrupt(&mut self, kind: InterruptKind, state: &CPUState) {
+        self.events.push(Event::Interrupt(kind, state.pc));
+    }
+}
+
+/// $0200: LDA #$42, STA $10, BRK
+fn observed_cpu() -> CPU<Memory, Recorder> {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in [0xA9, 0x42, 0x85, 0x10, 0x00].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu.write_byte(0xFFFE, 0x00);
+    cpu.write_byte(0xFFFF, 0x03);
+    cpu.write_byte(0x0300, 0xEA);
+    cpu.with_observer(Recorder::default())
+}
+
+#[test]
+pub fn test_observer_events() {
+    let mut cpu: CPU<Memory, Recorder> = observed_cpu();
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.observer().events, [
+        Event::Fetch(0x0200),
+        Event::Read(0x0200, 0xA9),
+        Event::Read(0x0201, 0x42),
+        Event::Executed(Mnemonic::LDA, 2),
+        Event::Fetch(0x0202),
+        Event::Read(0x0202, 0x85),
+        Event::Read(0x0203, 0x10),
+        Event::Write(0x0010, 0x42),
+        Event::Executed(Mnemonic::STA, 3),
+    ]);
+}
+
+#[test]
+pub fn test_observer_interrupts() {
+    let mut cpu: CPU<Memory, Recorder> = observed_cpu();
+    for _ in 0..3 {
+        cpu.run().unwrap();
+    }
+    cpu.set_nmi(true);
+    cpu.run().unwrap();
+
+    let interrupts: Vec<&Event> = cpu.observer().events.iter()
+        .filter(|event| matches!(event, Event::Interrupt(..)))
+        .collect();
+    assert_eq!(interrupts, [&Event::Interrupt(InterruptKind::Brk, 0x0206), &Event::Interrupt(InterruptKind::Nmi, 0x0300)]);
+}
+
+#[test]
+pub fn test_tick_delivers_events_once() {
+    let mut run_cpu: CPU<Memory, Recorder> = observed_cpu();
+    let mut tick_cpu: CPU<Memory, Recorder> = observed_cpu();
+    for _ in 0..4 {
+        run_cpu.run().unwrap();
+    }
+    while tick_cpu.get_stats().instructions() < 4 {
+        tick_cpu.tick().unwrap();
+    }
+
+    // bus events are reported per cycle, the others when the instruction completes
+    let events = |cpu: &CPU<Memory, Recorder>, bus: bool| -> Vec<Event> {
+        cpu.observer().events.iter()
+            .filter(|event| matches!(event, Event::Read(..) | Event::Write(..)) == bus)
+            .copied()
+            .collect()
+    };
+    assert_eq!(events(&tick_cpu, true), events(&run_cpu, true));
+    assert_eq!(events(&tick_cpu, false), events(&run_cpu, false));
+}
+
+#[test]
+pub fn test_boxed_observer() {
+    let mut cpu: CPU<Memory, Box<dyn Observer>> = CPU::new(CPUState::new(), Memory::new()).with_observer(Box::new(()));
+    cpu.run().unwrap();
+
+    cpu.observer_mut().on_read(0, 0);
+    assert_eq!(cpu.get_state().pc, 0);
+}
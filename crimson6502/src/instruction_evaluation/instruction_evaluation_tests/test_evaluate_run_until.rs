@@ -0,0 +1,95 @@
+use crate::BusAccess;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::RunState;
+use crate::StopReason;
+use crate::WatchKind;
+use crate::Watchpoint;
+use crate::CPU;
+
+/// $0200: LDX #$00; loop: INX, STX $10, JMP loop
+fn looping_cpu() -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in [0xA2, 0x00, 0xE8, 0x86, 0x10, 0x4C, 0x02, 0x02].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu
+}
+
+#[test]
+pub fn test_run_until_breakpoint() {
+    let mut cpu: CPU = looping_cpu();
+    cpu.add_breakpoint(0x0205);
+
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Breakpoint { addr: 0x0205 }));
+    assert_eq!(cpu.get_state().x, 1);
+    // continuing resumes past the breakpoint
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Breakpoint { addr: 0x0205 }));
+    assert_eq!(cpu.get_state().x, 2);
+}
+
+#[test]
+pub fn test_run_until_watchpoint() {
+    let mut cpu: CPU = looping_cpu();
+    cpu.add_watchpoint(Watchpoint::at(0x10, WatchKind::Read));
+    cpu.add_watchpoint(Watchpoint::new(0x0F, 0x11, WatchKind::Write));
+
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Watchpoint { access: BusAccess::write(0x10, 0x01) }));
+    assert_eq!(cpu.get_state().pc, 0x0205);
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Watchpoint { access: BusAccess::write(0x10, 0x02) }));
+
+    assert!(cpu.remove_watchpoint(&Watchpoint::new(0x0F, 0x11, WatchKind::Write)));
+    assert!(!cpu.remove_watchpoint(&Watchpoint::new(0x0F, 0x11, WatchKind::Write)));
+    // the read watchpoint is never hit
+    assert_eq!(cpu.run_until(100), Ok(StopReason::CycleBudget));
+}
+
+#[test]
+pub fn test_run_until_watchpoint_on_opcode_fetch() {
+    let mut cpu: CPU = looping_cpu();
+    cpu.add_watchpoint(Watchpoint::at(0x0202, WatchKind::ReadWrite));
+
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Watchpoint { access: BusAccess::read(0x0202, 0xE8) }));
+    assert_eq!(cpu.get_state().x, 1);
+}
+
+#[test]
+pub fn test_run_until_cycle_budget() {
+    let mut cpu: CPU = looping_cpu();
+
+    assert_eq!(cpu.run_until(0), Ok(StopReason::CycleBudget));
+    assert_eq!(cpu.get_stats().total_cycles(), 0);
+    // LDX # and 12 iterations of 8 cycles take 98 cycles, INX 100, the STX exceeds the budget
+    assert_eq!(cpu.run_until(101), Ok(StopReason::CycleBudget));
+    assert_eq!(cpu.get_stats().total_cycles(), 103);
+}
+
+#[test]
+pub fn test_run_until_illegal_opcode_and_halt() {
+    let mut cpu: CPU = looping_cpu();
+    cpu.set_undocumented_opcodes(false);
+    // SLO $10
+    cpu.write_byte(0x0203, 0x07);
+
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::IllegalOpcode { opcode: 0x07, addr: 0x0203 }));
+
+    cpu.set_undocumented_opcodes(true);
+    // JAM
+    cpu.write_byte(0x0203, 0x02);
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Halted { run_state: RunState::Jammed, addr: 0x0203 }));
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Halted { run_state: RunState::Jammed, addr: 0x0203 }));
+}
+
+#[test]
+pub fn test_run_until_waiting() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), CPUVariant::Wdc65C02);
+    // WAI
+    cpu.write_byte(0x0200, 0xCB);
+
+    assert_eq!(cpu.run_until(usize::MAX), Ok(StopReason::Waiting { addr: 0x0201 }));
+}
@@ -0,0 +1,73 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+use super::helpers::*;
+
+pub fn test_shift_accumulator_helper(mnemonic: Mnemonic, a: u8, sr: u8, expected_a: u8, expected_sr: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = a;
+    cpu_state.sr = sr;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    let mut expected_state = cpu_state;
+    expected_state.a = expected_a;
+    expected_state.sr = expected_sr;
+
+    assert_eq!(evaluate_shift(&mut cpu, mnemonic, AddressingMode::Accumulator).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn test_shift_memory_helper(mnemonic: Mnemonic, value: u8, sr: u8, addr: u16, addressing_mode: AddressingMode, index_value: u8, expected_value: u8, expected_sr: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.sr = sr;
+    maybe_apply_index_value(addressing_mode, &mut cpu_state, index_value);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    let effective_addr: u16 = compute_effective_address(addressing_mode, index_value, addr, &cpu);
+    cpu.write_byte(0, addr as u8);
+    cpu.write_byte(1, (addr >> 8) as u8);
+    cpu.write_byte(effective_addr, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.sr = expected_sr;
+    expected_state.pc = addressing_mode.instruction_length() as u16;
+
+    let expected_cycles: u8 = match addressing_mode {
+        AddressingMode::ZeroPage => 5,
+        AddressingMode::ZeroPageX | AddressingMode::Absolute => 6,
+        _ => 7,
+    };
+
+    assert_eq!(evaluate_shift(&mut cpu, mnemonic, addressing_mode).unwrap(), expected_cycles);
+    assert_eq!(*cpu.get_state(), expected_state);
+    assert_eq!(cpu.read_byte(effective_addr), expected_value);
+}
+
+#[test]
+pub fn test_shift_accumulator() {
+    test_shift_accumulator_helper(Mnemonic::ASL, 0x81, 0, 0x02, flag!(carry));
+    test_shift_accumulator_helper(Mnemonic::ASL, 0x40, flag!(carry), 0x80, flag!(negative));
+    test_shift_accumulator_helper(Mnemonic::LSR, 0x01, 0, 0x00, flag!(carry) | flag!(zero));
+    test_shift_accumulator_helper(Mnemonic::LSR, 0x80, flag!(carry), 0x40, 0);
+    test_shift_accumulator_helper(Mnemonic::ROL, 0x80, flag!(carry), 0x01, flag!(carry));
+    test_shift_accumulator_helper(Mnemonic::ROL, 0x40, 0, 0x80, flag!(negative));
+    test_shift_accumulator_helper(Mnemonic::ROR, 0x01, flag!(carry), 0x80, flag!(carry) | flag!(negative));
+    test_shift_accumulator_helper(Mnemonic::ROR, 0x01, 0, 0x00, flag!(carry) | flag!(zero));
+}
+
+#[test]
+pub fn test_shift_memory() {
+    test_shift_memory_helper(Mnemonic::ASL, 0xC0, 0, 0x0040, AddressingMode::ZeroPage, 0, 0x80, flag!(carry) | flag!(negative));
+    test_shift_memory_helper(Mnemonic::LSR, 0x03, 0, 0x0040, AddressingMode::ZeroPageX, 0x10, 0x01, flag!(carry));
+    test_shift_memory_helper(Mnemonic::ROL, 0x7F, flag!(carry), 0x1234, AddressingMode::Absolute, 0, 0xFF, flag!(negative));
+    test_shift_memory_helper(Mnemonic::ROR, 0x02, flag!(carry), 0x1234, AddressingMode::AbsoluteX, 0x01, 0x81, flag!(negative));
+}
+
+#[test]
+pub fn test_shift_absolute_indexed_ignores_page_cross() {
+    test_shift_memory_helper(Mnemonic::ASL, 0x01, 0, 0x1200, AddressingMode::AbsoluteX, 0x01, 0x02, 0);
+    test_shift_memory_helper(Mnemonic::ASL, 0x01, 0, 0x12FF, AddressingMode::AbsoluteX, 0x01, 0x02, 0);
+}
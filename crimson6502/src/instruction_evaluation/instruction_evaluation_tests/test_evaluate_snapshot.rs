@@ -0,0 +1,116 @@
+use crate::Bus;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::memory_map::*;
+use crate::snapshot::*;
+
+/// countdown loop at $0200: LDX #$10, INC $00,X, DEX, BNE -5, BRK
+fn snapshot_cpu() -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFD;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    for (i, byte) in [0xA2, 0x10, 0xF6, 0x00, 0xCA, 0xD0, 0xFB, 0x00].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu
+}
+
+fn run_instructions<B: Bus>(cpu: &mut CPU<B>, instructions: usize) {
+    for _ in 0..instructions {
+        cpu.run().unwrap();
+    }
+}
+
+#[test]
+pub fn test_binary_round_trip() {
+    let mut cpu: CPU = snapshot_cpu();
+    run_instructions(&mut cpu, 10);
+    cpu.set_irq(true);
+    cpu.set_nmi(true);
+    let saved: Vec<u8> = cpu.save_state().unwrap();
+    run_instructions(&mut cpu, 10);
+    let expected_state: CPUState = *cpu.get_state();
+
+    let mut restored: CPU = CPU::new(CPUState::new(), Memory::new());
+    restored.load_state(&saved).unwrap();
+    assert_eq!(restored.snapshot().unwrap(), Snapshot::from_bytes(&saved).unwrap());
+    assert!(restored.nmi_pending());
+    run_instructions(&mut restored, 10);
+    assert_eq!(*restored.get_state(), expected_state);
+    assert_eq!(restored.bus(), cpu.bus());
+    assert_eq!(restored.get_stats(), cpu.get_stats());
+}
+
+#[test]
+pub fn test_json_round_trip() {
+    let mut cpu: CPU = CPU::with_variant(CPUState::new(), Memory::with_banks(2), CPUVariant::Wdc65C816);
+    cpu.write_byte_long(0x01ABCD, 0x5A);
+    run_instructions(&mut cpu, 3);
+    let snapshot: Snapshot = cpu.snapshot().unwrap();
+    let json: String = cpu.save_state_json().unwrap();
+
+    assert!(json.contains("\"format\": \"crimson6502 snapshot\""));
+    assert_eq!(Snapshot::from_json(&json).unwrap(), snapshot);
+    let mut restored: CPU = CPU::new(CPUState::new(), Memory::new());
+    restored.load_state_json(&json).unwrap();
+    assert_eq!(restored.variant(), CPUVariant::Wdc65C816);
+    assert_eq!(restored.peek_byte_long(0x01ABCD), 0x5A);
+}
+
+#[test]
+pub fn test_header_checks() {
+    let cpu: CPU = snapshot_cpu();
+    let mut saved: Vec<u8> = cpu.save_state().unwrap();
+
+    assert_eq!(Snapshot::from_bytes(b"not a snapshot"), Err(SnapshotError::NotASnapshot));
+    assert_eq!(Snapshot::from_bytes(&saved[..saved.len() - 1]), Err(SnapshotError::Truncated));
+    saved[SNAPSHOT_MAGIC.len()] = 2;
+    assert_eq!(Snapshot::from_bytes(&saved), Err(SnapshotError::UnsupportedVersion(2)));
+
+    let json: String = cpu.save_state_json().unwrap().replacen("\"version\": 1", "\"version\": 2", 1);
+    assert_eq!(Snapshot::from_json(&json), Err(SnapshotError::UnsupportedVersion(2)));
+    assert_eq!(Snapshot::from_json("{\"a\": 1}"), Err(SnapshotError::NotASnapshot));
+}
+
+#[test]
+pub fn test_restore_rejects_mismatched_bus() {
+    let cpu: CPU = CPU::new(CPUState::new(), Memory::with_banks(2));
+    let saved: Vec<u8> = cpu.save_state().unwrap();
+    let description: MemoryMapDescription = MemoryMapDescription::new().ram(0x0000, 0xFFFF);
+    let mut mapped_cpu: CPU<MemoryMap> = CPU::new(CPUState::new(), MemoryMap::new(&description).unwrap());
+
+    assert_eq!(mapped_cpu.load_state(&saved), Err(SnapshotError::UnsupportedBus));
+}
+
+#[test]
+pub fn test_memory_map_snapshot() {
+    let description: MemoryMapDescription = MemoryMapDescription::new()
+        .ram(0x0000, 0x3FFF)
+        .banked_rom(0x8000, 0xBFFF, 4, 0x3FFE)
+        .rom(0xC000, 0xFFFF);
+    let mut memory_map: MemoryMap = MemoryMap::new(&description).unwrap();
+    memory_map.load_bank(0x8000, 2, &[0xEA]).unwrap();
+    let mut cpu: CPU<MemoryMap> = CPU::new(CPUState::new(), memory_map);
+    cpu.write_byte(0x3FFE, 2);
+    cpu.write_byte(0x1234, 0x77);
+    let saved: Vec<u8> = cpu.save_state().unwrap();
+
+    let mut restored: CPU<MemoryMap> = CPU::new(CPUState::new(), MemoryMap::new(&description).unwrap());
+    restored.load_state(&saved).unwrap();
+    assert_eq!(restored.peek_byte(0x8000), 0xEA);
+    assert_eq!(restored.peek_byte(0x1234), 0x77);
+    assert_eq!(restored.bus().mapped_banks(), cpu.bus().mapped_banks());
+}
+
+#[test]
+pub fn test_snapshot_between_instructions() {
+    let mut cpu: CPU = snapshot_cpu();
+    cpu.tick().unwrap();
+
+    assert_eq!(cpu.snapshot(), Err(SnapshotError::InstructionInProgress));
+    cpu.tick().unwrap();
+    assert!(cpu.snapshot().is_ok());
+}
@@ -0,0 +1,78 @@
+use crate::CPUState;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+#[test]
+pub fn test_reset_stack_pointer() {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    cpu.init_state();
+    assert_eq!(cpu.get_state().s, 0xFD);
+    assert_eq!(cpu.get_state().sr & flag!(interrupt), flag!(interrupt));
+}
+
+#[test]
+pub fn test_push_pull_wraps_in_page_one() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.s = 0x00;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    cpu.push_byte(0x11);
+    cpu.push_byte(0x22);
+    assert_eq!(cpu.read_byte(0x0100), 0x11);
+    assert_eq!(cpu.read_byte(0x01FF), 0x22);
+    assert_eq!(cpu.get_state().s, 0xFE);
+
+    assert_eq!(cpu.pull_byte(), 0x22);
+    assert_eq!(cpu.pull_byte(), 0x11);
+    assert_eq!(cpu.get_state().s, 0x00);
+}
+
+#[test]
+pub fn test_pha_pla() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.s = 0xFF;
+    cpu_state.a = 0x80;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    let cycles: u8 = evaluate_stack(&mut cpu, Mnemonic::PHA, AddressingMode::Implied).unwrap();
+    assert_eq!(cpu.read_byte(0x01FF), 0x80);
+    assert_eq!(cpu.get_state().s, 0xFE);
+    assert_eq!(cycles, 3);
+
+    let mut pulled_state = *cpu.get_state();
+    pulled_state.a = 0x00;
+    let mut cpu: CPU = CPU::new(pulled_state, Memory::new());
+    cpu.write_byte(0x01FF, 0x80);
+
+    let mut expected_state = pulled_state;
+    expected_state.a = 0x80;
+    expected_state.s = 0xFF;
+    expected_state.sr = flag!(negative);
+
+    assert_eq!(evaluate_stack(&mut cpu, Mnemonic::PLA, AddressingMode::Implied).unwrap(), 4);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_php_plp_break_and_unused_bits() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(carry) | flag!(negative);
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+
+    evaluate_stack(&mut cpu, Mnemonic::PHP, AddressingMode::Implied).unwrap();
+    assert_eq!(cpu.read_byte(0x01FF), flag!(carry) | flag!(negative) | flag!(brk) | flag!(unused));
+
+    let mut cpu_state = CPUState::new();
+    cpu_state.s = 0xFE;
+    cpu_state.sr = flag!(unused);
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.write_byte(0x01FF, 0xFF);
+
+    let cycles: u8 = evaluate_stack(&mut cpu, Mnemonic::PLP, AddressingMode::Implied).unwrap();
+    assert_eq!(cpu.get_state().sr, !flag!(brk));
+    assert_eq!(cpu.get_state().s, 0xFF);
+    assert_eq!(cycles, 4);
+}
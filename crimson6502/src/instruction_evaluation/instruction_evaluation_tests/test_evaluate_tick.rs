@@ -0,0 +1,238 @@
+use crate::BusAccess;
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+
+fn tick_cpu(variant: CPUVariant, program: &[u8], x: u8) -> CPU {
+    let mut cpu_state = CPUState::new();
+    cpu_state.x = x;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), variant);
+    for (i, byte) in program.iter().enumerate() {
+        cpu.write_byte(i as u16, *byte);
+    }
+    cpu.set_bus_log(true);
+    cpu
+}
+
+#[test]
+pub fn test_read_modify_write_cycles() {
+    // INC $10
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xE6, 0x10], 0);
+    cpu.write_byte(0x10, 0x41);
+    cpu.take_bus_log();
+    let expected: [BusAccess; 5] = [
+        BusAccess::read(0x0000, 0xE6),
+        BusAccess::read(0x0001, 0x10),
+        BusAccess::read(0x0010, 0x41),
+        BusAccess::write(0x0010, 0x41),
+        BusAccess::write(0x0010, 0x42),
+    ];
+
+    for cycle in 1..=5 {
+        cpu.tick().unwrap();
+        assert_eq!(cpu.bus_log(), &expected[..cycle]);
+        assert_eq!(cpu.instruction_in_progress(), cycle < 5);
+    }
+    assert_eq!(cpu.get_state().pc, 2);
+    assert_eq!(cpu.get_stats().total_cycles(), 5);
+    assert_eq!(cpu.get_stats().instructions(), 1);
+}
+
+#[test]
+pub fn test_registers_update_on_last_cycle() {
+    // LDA #$12
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xA9, 0x12], 0);
+    cpu.tick().unwrap();
+
+    assert_eq!(cpu.get_state().a, 0);
+    assert_eq!(cpu.get_state().pc, 0);
+    assert_eq!(cpu.get_stats().total_cycles(), 0);
+    cpu.tick().unwrap();
+    assert_eq!(cpu.get_state().a, 0x12);
+    assert_eq!(cpu.get_state().pc, 2);
+    assert!(!cpu.instruction_in_progress());
+}
+
+#[test]
+pub fn test_page_cross_dummy_read() {
+    // LDA $20FF,X
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xBD, 0xFF, 0x20], 1);
+    cpu.write_byte(0x2000, 0x11);
+    cpu.write_byte(0x2100, 0x22);
+    cpu.take_bus_log();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().a, 0x22);
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0000, 0xBD),
+        BusAccess::read(0x0001, 0xFF),
+        BusAccess::read(0x0002, 0x20),
+        BusAccess::read(0x2000, 0x11),
+        BusAccess::read(0x2100, 0x22),
+    ]);
+}
+
+#[test]
+pub fn test_cmos_dummy_accesses() {
+    // LDA $20FF,X, INC $10
+    let mut cpu: CPU = tick_cpu(CPUVariant::Cmos65C02, &[0xBD, 0xFF, 0x20, 0xE6, 0x10], 1);
+    cpu.run().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0000, 0xBD),
+        BusAccess::read(0x0001, 0xFF),
+        BusAccess::read(0x0002, 0x20),
+        BusAccess::read(0x0002, 0x20),
+        BusAccess::read(0x2100, 0x00),
+        BusAccess::read(0x0003, 0xE6),
+        BusAccess::read(0x0004, 0x10),
+        BusAccess::read(0x0010, 0x00),
+        BusAccess::read(0x0010, 0x00),
+        BusAccess::write(0x0010, 0x01),
+    ]);
+}
+
+#[test]
+pub fn test_run_completes_ticked_instruction() {
+    // INC $10, INX
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xE6, 0x10, 0xE8], 0);
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+    cpu.run().unwrap();
+
+    assert_eq!(cpu.get_state().pc, 2);
+    assert_eq!(cpu.peek_byte(0x10), 1);
+    assert_eq!(cpu.bus_log().len(), 5);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().x, 1);
+}
+
+#[test]
+pub fn test_interrupt_cycles() {
+    // NOP
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xEA], 0);
+    cpu.write_byte(0xFFFE, 0x00);
+    cpu.write_byte(0xFFFF, 0x80);
+    cpu.set_irq(true);
+    for _ in 0..6 {
+        cpu.tick().unwrap();
+        assert!(cpu.instruction_in_progress());
+    }
+    cpu.tick().unwrap();
+
+    assert!(!cpu.instruction_in_progress());
+    assert_eq!(cpu.get_state().pc, 0x8000);
+    assert_eq!(cpu.get_stats().total_cycles(), 7);
+}
+
+#[test]
+pub fn test_nmi_during_ticked_instruction() {
+    // NOP, NOP
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xEA, 0xEA], 0);
+    cpu.write_byte(0xFFFA, 0x00);
+    cpu.write_byte(0xFFFB, 0x90);
+    cpu.tick().unwrap();
+    cpu.set_nmi(true);
+    cpu.tick().unwrap();
+
+    // the NOP completes and the NMI stays latched for the next instruction
+    assert!(!cpu.instruction_in_progress());
+    assert!(cpu.nmi_pending());
+    assert_eq!(cpu.get_state().pc, 1);
+    for _ in 0..7 {
+        cpu.tick().unwrap();
+    }
+    assert!(!cpu.nmi_pending());
+    assert_eq!(cpu.get_state().pc, 0x9000);
+}
+
+#[test]
+pub fn test_irq_during_ticked_instruction() {
+    // LDA #$42, NOP
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xA9, 0x42, 0xEA], 0);
+    cpu.write_byte(0xFFFE, 0x00);
+    cpu.write_byte(0xFFFF, 0x80);
+    cpu.take_bus_log();
+    cpu.tick().unwrap();
+    cpu.set_irq(true);
+    cpu.tick().unwrap();
+
+    // the LDA completes, reading its opcode once, and the IRQ is serviced after it
+    assert!(!cpu.instruction_in_progress());
+    assert_eq!(cpu.get_state().a, 0x42);
+    assert_eq!(cpu.get_state().pc, 2);
+    assert_eq!(cpu.take_bus_log(), [
+        BusAccess::read(0x0000, 0xA9),
+        BusAccess::read(0x0001, 0x42),
+    ]);
+    for _ in 0..7 {
+        cpu.tick().unwrap();
+    }
+    assert_eq!(cpu.get_state().pc, 0x8000);
+}
+
+#[test]
+pub fn test_reset_during_ticked_instruction() {
+    // INC $10, reset to $0300: NOP
+    let mut cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &[0xE6, 0x10], 0);
+    cpu.write_byte(0xFFFC, 0x00);
+    cpu.write_byte(0xFFFD, 0x03);
+    cpu.write_byte(0x0300, 0xEA);
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+    cpu.init_state();
+
+    // the reset vector is read from the bus, the INC is abandoned
+    assert!(!cpu.instruction_in_progress());
+    assert_eq!(cpu.get_state().pc, 0x0300);
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+    assert!(!cpu.instruction_in_progress());
+    assert_eq!(cpu.get_state().pc, 0x0301);
+    assert_eq!(cpu.peek_byte(0x10), 0x00);
+
+    // a breakpoint reported before the reset stops the CPU again
+    cpu.add_breakpoint(0x0300);
+    cpu.init_state();
+    assert!(cpu.run().is_err());
+    cpu.init_state();
+    assert!(cpu.run().is_err());
+}
+
+#[test]
+pub fn test_tick_matches_run() {
+    let program: [u8; 0x26] = [
+        // $00: LDX #$05, LDA $20FE,X, STA $30,X, INC $40, ASL $41, DEX, BNE $02
+        0xA2, 0x05, 0xBD, 0xFE, 0x20, 0x95, 0x30, 0xE6, 0x40, 0x06, 0x41, 0xCA, 0xD0, 0xF4,
+        // $0E: JSR $20, JMP $11
+        0x20, 0x20, 0x00, 0x4C, 0x11, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        // $20: LDY #$03, LDA ($50),Y, RTS
+        0xA0, 0x03, 0xB1, 0x50, 0x60, 0x00,
+    ];
+    let mut run_cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &program, 0);
+    let mut tick_cpu: CPU = tick_cpu(CPUVariant::Nmos6502, &program, 0);
+    for cpu in [&mut run_cpu, &mut tick_cpu] {
+        cpu.write_byte(0x41, 0x01);
+        cpu.write_byte(0x50, 0xFE);
+        cpu.write_byte(0x51, 0x20);
+        for addr in 0x2000..0x2110 {
+            cpu.write_byte(addr, addr as u8);
+        }
+        cpu.take_bus_log();
+    }
+
+    for _ in 0..40 {
+        run_cpu.run().unwrap();
+    }
+    while tick_cpu.get_stats().instructions() < 40 {
+        tick_cpu.tick().unwrap();
+    }
+
+    assert_eq!(tick_cpu.get_state(), run_cpu.get_state());
+    assert_eq!(tick_cpu.get_stats(), run_cpu.get_stats());
+    assert_eq!(tick_cpu.bus_log(), run_cpu.bus_log());
+    assert_eq!(tick_cpu.bus(), run_cpu.bus());
+}
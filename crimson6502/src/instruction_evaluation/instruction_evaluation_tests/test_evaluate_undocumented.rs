@@ -0,0 +1,128 @@
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::CpuError;
+use crate::RunState;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+pub fn test_undocumented_immediate_helper(mnemonic: Mnemonic, state: CPUState, value: u8, expected_state: CPUState) {
+    let mut cpu: CPU = CPU::new(state, Memory::new());
+    cpu.write_byte(0, value);
+
+    let mut expected_state = expected_state;
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_undocumented(&mut cpu, mnemonic, AddressingMode::Immediate).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+pub fn test_undocumented_zeropage_helper(mnemonic: Mnemonic, state: CPUState, value: u8, expected_state: CPUState, expected_value: u8, expected_cycles: u8) {
+    let mut cpu: CPU = CPU::new(state, Memory::new());
+    cpu.write_byte(0, 0x40);
+    cpu.write_byte(0x40, value);
+
+    let mut expected_state = expected_state;
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_undocumented(&mut cpu, mnemonic, AddressingMode::ZeroPage).unwrap(), expected_cycles);
+    assert_eq!(*cpu.get_state(), expected_state);
+    assert_eq!(cpu.read_byte(0x40), expected_value);
+}
+
+fn state_with(a: u8, x: u8, sr: u8) -> CPUState {
+    let mut state = CPUState::new();
+    state.a = a;
+    state.x = x;
+    state.sr = sr;
+    state
+}
+
+#[test]
+pub fn test_lax_sax() {
+    test_undocumented_zeropage_helper(Mnemonic::LAX, state_with(0, 0, 0), 0x85, state_with(0x85, 0x85, flag!(negative)), 0x85, 3);
+    test_undocumented_zeropage_helper(Mnemonic::SAX, state_with(0xF0, 0x3C, 0), 0x00, state_with(0xF0, 0x3C, 0), 0x30, 3);
+}
+
+#[test]
+pub fn test_combined_read_modify_write() {
+    // SLO: ASL memory, then ORA
+    test_undocumented_zeropage_helper(Mnemonic::SLO, state_with(0x01, 0, 0), 0x81, state_with(0x03, 0, flag!(carry)), 0x02, 5);
+    // RLA: ROL memory, then AND
+    test_undocumented_zeropage_helper(Mnemonic::RLA, state_with(0x0F, 0, flag!(carry)), 0x04, state_with(0x09, 0, 0), 0x09, 5);
+    // SRE: LSR memory, then EOR
+    test_undocumented_zeropage_helper(Mnemonic::SRE, state_with(0xFF, 0, 0), 0x03, state_with(0xFE, 0, flag!(carry) | flag!(negative)), 0x01, 5);
+    // RRA: ROR memory, then ADC with the carry shifted out
+    test_undocumented_zeropage_helper(Mnemonic::RRA, state_with(0x10, 0, 0), 0x05, state_with(0x13, 0, 0), 0x02, 5);
+    // DCP: DEC memory, then CMP
+    test_undocumented_zeropage_helper(Mnemonic::DCP, state_with(0x41, 0, 0), 0x42, state_with(0x41, 0, flag!(carry) | flag!(zero)), 0x41, 5);
+    // ISC: INC memory, then SBC
+    test_undocumented_zeropage_helper(Mnemonic::ISC, state_with(0x50, 0, flag!(carry)), 0x0F, state_with(0x40, 0, flag!(carry)), 0x10, 5);
+}
+
+#[test]
+pub fn test_immediate_combinations() {
+    test_undocumented_immediate_helper(Mnemonic::ANC, state_with(0xFF, 0, 0), 0x80, state_with(0x80, 0, flag!(negative) | flag!(carry)));
+    test_undocumented_immediate_helper(Mnemonic::ALR, state_with(0xFF, 0, 0), 0x03, state_with(0x01, 0, flag!(carry)));
+    test_undocumented_immediate_helper(Mnemonic::ARR, state_with(0xFF, 0, flag!(carry)), 0xC0, state_with(0xE0, 0, flag!(negative) | flag!(carry)));
+    test_undocumented_immediate_helper(Mnemonic::ARR, state_with(0xFF, 0, 0), 0x40, state_with(0x20, 0, flag!(overflow)));
+    test_undocumented_immediate_helper(Mnemonic::SBX, state_with(0xF0, 0x3C, 0), 0x10, state_with(0xF0, 0x20, flag!(carry)));
+    test_undocumented_immediate_helper(Mnemonic::SBX, state_with(0xF0, 0x3C, 0), 0x31, state_with(0xF0, 0xFF, flag!(negative)));
+}
+
+#[test]
+pub fn test_multi_byte_nops() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    // NOP #imm, NOP zp, NOP abs,X
+    for (i, byte) in [0x80, 0x12, 0x04, 0x34, 0x1C, 0xFF, 0x12].iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x0202);
+    cpu.run().unwrap();
+    assert_eq!(cpu.get_state().pc, 0x0204);
+    cpu.run().unwrap();
+
+    // registers and flags are untouched
+    let mut expected_state = cpu_state;
+    expected_state.pc = 0x0207;
+    assert_eq!(*cpu.get_state(), expected_state);
+}
+
+#[test]
+pub fn test_jam_halts_until_reset() {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    cpu.write_byte(0, 0x02);
+    assert_eq!(cpu.run(), Err(CpuError::Halted { run_state: RunState::Jammed, addr: 0 }));
+    assert_eq!(cpu.run_state(), RunState::Jammed);
+    assert_eq!(cpu.get_state().pc, 0);
+
+    assert_eq!(cpu.run(), Err(CpuError::Halted { run_state: RunState::Jammed, addr: 0 }));
+    assert_eq!(cpu.get_state().pc, 0);
+
+    cpu.write_byte(crate::RESET_VECTOR, 0x00);
+    cpu.write_byte(crate::RESET_VECTOR + 1, 0x80);
+    cpu.init_state();
+    assert_eq!(cpu.run_state(), RunState::Running);
+    assert_eq!(cpu.get_state().pc, 0x8000);
+}
+
+#[test]
+pub fn test_every_opcode_decodes() {
+    for variant in [CPUVariant::Nmos6502, CPUVariant::Cmos65C02, CPUVariant::Wdc65C02, CPUVariant::Wdc65C816, CPUVariant::Ricoh2A03] {
+        for byte in 0..=255u8 {
+            assert!(Instruction::from_byte(byte, variant).is_some(), "opcode {:#04X} does not decode on {:?}", byte, variant);
+        }
+    }
+}
+
+#[test]
+pub fn test_undocumented_opcodes_disabled() {
+    let mut cpu: CPU = CPU::new(CPUState::new(), Memory::new());
+    cpu.set_undocumented_opcodes(false);
+    cpu.write_byte(0, 0xA7);
+    assert_eq!(cpu.run(), Err(CpuError::IllegalOpcode { opcode: 0xA7, addr: 0 }));
+}
@@ -0,0 +1,201 @@
+use crate::CPUState;
+use crate::CPUVariant;
+use crate::Memory;
+use crate::CPU;
+use crate::instruction::*;
+use crate::instruction_evaluation::*;
+
+fn cmos_cpu(state: CPUState) -> CPU {
+    CPU::with_variant(state, Memory::new(), CPUVariant::Cmos65C02)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn test_arithmetic_variant_helper(variant: CPUVariant, mnemonic: Mnemonic, a: u8, sr: u8, value: u8, expected_a: u8, expected_sr: u8, expected_cycles: u8) {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = a;
+    cpu_state.sr = sr;
+    let mut cpu: CPU = CPU::with_variant(cpu_state, Memory::new(), variant);
+    cpu.write_byte(0, value);
+
+    let mut expected_state = cpu_state;
+    expected_state.a = expected_a;
+    expected_state.sr = expected_sr;
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_arithmetic(&mut cpu, mnemonic, AddressingMode::Immediate).unwrap(), expected_cycles);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_cmos_decodes_no_illegal_opcodes() {
+    for byte in 0..=255u8 {
+        let instruction: Instruction = Instruction::from_byte(byte, CPUVariant::Cmos65C02).unwrap();
+        assert_eq!(instruction.opcode, byte);
+        assert!(!instruction.undocumented, "opcode {:#04X} decodes to undocumented {:?}", byte, instruction.mnemonic);
+    }
+}
+
+#[test]
+pub fn test_ricoh_decodes_like_nmos() {
+    for byte in 0..=255u8 {
+        let nmos: Instruction = Instruction::from_byte(byte, CPUVariant::Nmos6502).unwrap();
+        let ricoh: Instruction = Instruction::from_byte(byte, CPUVariant::Ricoh2A03).unwrap();
+        assert_eq!(format!("{:?}", nmos), format!("{:?}", ricoh));
+    }
+}
+
+#[test]
+pub fn test_cmos_jmp_indirect_without_page_wrap() {
+    let mut cpu: CPU = cmos_cpu(CPUState::new());
+    cpu.write_byte(0, 0xFF);
+    cpu.write_byte(1, 0x12);
+    cpu.write_byte(0x12FF, 0x34);
+    cpu.write_byte(0x1300, 0x56);
+    cpu.write_byte(0x1200, 0x78);
+
+    let mut expected_state = CPUState::new();
+    expected_state.pc = 0x5634;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JMP, AddressingMode::Indirect).unwrap(), 6);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_cmos_jmp_absolute_indirect_x() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.x = 0x04;
+    let mut cpu: CPU = cmos_cpu(cpu_state);
+    cpu.write_byte(0, 0x00);
+    cpu.write_byte(1, 0x20);
+    cpu.write_byte(0x2004, 0xCD);
+    cpu.write_byte(0x2005, 0xAB);
+
+    let mut expected_state = cpu_state;
+    expected_state.pc = 0xABCD;
+
+    assert_eq!(evaluate_jump(&mut cpu, Mnemonic::JMP, AddressingMode::AbsoluteIndirectX).unwrap(), 6);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_cmos_program() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.pc = 0x0200;
+    cpu_state.s = 0xFF;
+    let mut cpu: CPU = cmos_cpu(cpu_state);
+    let program: [u8; 19] = [
+        0xA2, 0x42,         // LDX #$42
+        0xDA,               // PHX
+        0x7A,               // PLY
+        0x64, 0x40,         // STZ $40
+        0xA9, 0x0F,         // LDA #$0F
+        0x04, 0x40,         // TSB $40
+        0xA9, 0x03,         // LDA #$03
+        0x14, 0x40,         // TRB $40
+        0xB2, 0x50,         // LDA ($50)
+        0x80, 0x01,         // BRA +1
+        0xEA,               // NOP, skipped
+    ];
+    for (i, byte) in program.iter().enumerate() {
+        cpu.write_byte(0x0200 + i as u16, *byte);
+    }
+    cpu.write_byte(0x40, 0xFF);
+    cpu.write_byte(0x50, 0x00);
+    cpu.write_byte(0x51, 0x03);
+    cpu.write_byte(0x0300, 0x99);
+
+    for _ in 0..10 {
+        cpu.run().unwrap();
+    }
+
+    assert_eq!(cpu.get_state().y, 0x42);
+    assert_eq!(cpu.read_byte(0x40), 0x0C);
+    assert_eq!(cpu.get_state().a, 0x99);
+    assert_eq!(cpu.get_state().pc, 0x0213);
+    assert_eq!(cpu.get_state().s, 0xFF);
+}
+
+#[test]
+pub fn test_cmos_bit_immediate_only_sets_zero() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.a = 0x01;
+    let mut cpu: CPU = cmos_cpu(cpu_state);
+    cpu.write_byte(0, 0xC0);
+
+    let mut expected_state = cpu_state;
+    expected_state.sr = flag!(zero);
+    expected_state.pc = 1;
+
+    assert_eq!(evaluate_logical(&mut cpu, Mnemonic::BIT, AddressingMode::Immediate).unwrap(), 2);
+    assert_eq!(*cpu.get_state(), expected_state)
+}
+
+#[test]
+pub fn test_cmos_decimal_flags() {
+    // 0x99 + 0x01 = 0x00 carry: NMOS takes Z from the binary sum and N from the intermediate result
+    test_arithmetic_variant_helper(CPUVariant::Nmos6502, Mnemonic::ADC, 0x99, flag!(decimal), 0x01, 0x00, flag!(decimal) | flag!(carry) | flag!(negative), 2);
+    test_arithmetic_variant_helper(CPUVariant::Cmos65C02, Mnemonic::ADC, 0x99, flag!(decimal), 0x01, 0x00, flag!(decimal) | flag!(carry) | flag!(zero), 3);
+    // 0x10 - 0x01 = 0x09
+    test_arithmetic_variant_helper(CPUVariant::Cmos65C02, Mnemonic::SBC, 0x10, flag!(decimal) | flag!(carry), 0x01, 0x09, flag!(decimal) | flag!(carry), 3);
+    // 0x00 - 0x01 = 0x99 borrow
+    test_arithmetic_variant_helper(CPUVariant::Cmos65C02, Mnemonic::SBC, 0x00, flag!(decimal) | flag!(carry), 0x01, 0x99, flag!(decimal) | flag!(negative), 3);
+}
+
+#[test]
+pub fn test_ricoh_ignores_decimal_flag() {
+    test_arithmetic_variant_helper(CPUVariant::Ricoh2A03, Mnemonic::ADC, 0x09, flag!(decimal), 0x01, 0x0A, flag!(decimal), 2);
+    test_arithmetic_variant_helper(CPUVariant::Ricoh2A03, Mnemonic::SBC, 0x10, flag!(decimal) | flag!(carry), 0x01, 0x0F, flag!(decimal) | flag!(carry), 2);
+}
+
+#[test]
+pub fn test_cmos_interrupt_clears_decimal() {
+    let mut cpu_state = CPUState::new();
+    cpu_state.s = 0xFF;
+    cpu_state.sr = flag!(decimal);
+    let mut cpu: CPU = cmos_cpu(cpu_state);
+    cpu.enter_interrupt(crate::IRQ_VECTOR, false);
+    assert_eq!(cpu.get_state().sr & flag!(decimal), 0);
+
+    let mut cpu: CPU = CPU::new(cpu_state, Memory::new());
+    cpu.enter_interrupt(crate::IRQ_VECTOR, false);
+    assert_eq!(cpu.get_state().sr & flag!(decimal), flag!(decimal));
+}
+
+#[test]
+pub fn test_cmos_single_cycle_nop() {
+    let mut cpu: CPU = cmos_cpu(CPUState::new());
+    cpu.write_byte(0, 0x03);
+    let cycles: u8 = evaluate_nop(&mut cpu, 0x03, AddressingMode::Implied).unwrap();
+    assert_eq!(cycles, 1);
+
+    // $02 is JAM on NMOS but a two byte NOP on the 65C02
+    cpu.write_byte(0, 0x02);
+    cpu.run().unwrap();
+    assert_eq!(cpu.run_state(), crate::RunState::Running);
+    assert_eq!(cpu.get_state().pc, 2);
+}
+
+#[test]
+pub fn test_cmos_nops_run_without_undocumented_opcodes() {
+    let program: [u8; 17] = [
+        0x03,               // NOP
+        0x0B,               // NOP
+        0x02, 0x00,         // NOP #$00
+        0x44, 0x00,         // NOP $00
+        0x54, 0x00,         // NOP $00,X
+        0x5C, 0x00, 0x00,   // NOP $0000
+        0xDC, 0x00, 0x00,   // NOP $0000
+        0xFC, 0x00, 0x00,   // NOP $0000
+    ];
+    for variant in [CPUVariant::Cmos65C02, CPUVariant::Wdc65C02] {
+        let mut cpu: CPU = CPU::with_variant(CPUState::new(), Memory::new(), variant);
+        cpu.set_undocumented_opcodes(false);
+        for (i, byte) in program.iter().enumerate() {
+            cpu.write_byte(i as u16, *byte);
+        }
+        for _ in 0..8 {
+            cpu.run().unwrap();
+        }
+        assert_eq!(cpu.get_state().pc, program.len() as u16, "{:?}", variant);
+    }
+}
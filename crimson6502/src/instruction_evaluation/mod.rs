@@ -1,10 +1,39 @@
 pub mod types;
+pub mod flags;
+pub mod evaluate_arithmetic;
+pub mod evaluate_bit_manipulation;
+pub mod evaluate_block_move;
+pub mod evaluate_branch;
+pub mod evaluate_compare;
+pub mod evaluate_flag;
+pub mod evaluate_increment;
+pub mod evaluate_interrupt;
+pub mod evaluate_jump;
+pub mod evaluate_logical;
 pub mod evaluate_other;
+pub mod evaluate_shift;
+pub mod evaluate_stack;
 pub mod evaluate_transfer;
+pub mod evaluate_undocumented;
+pub mod evaluate_wide;
 
 pub use types::InstructionResult;
+pub use evaluate_arithmetic::*;
+pub use evaluate_bit_manipulation::*;
+pub use evaluate_block_move::*;
+pub use evaluate_branch::*;
+pub use evaluate_compare::*;
+pub use evaluate_flag::*;
+pub use evaluate_increment::*;
+pub use evaluate_interrupt::*;
+pub use evaluate_jump::*;
+pub use evaluate_logical::*;
 pub use evaluate_other::*;
+pub use evaluate_shift::*;
+pub use evaluate_stack::*;
 pub use evaluate_transfer::*;
+pub use evaluate_undocumented::*;
+pub use evaluate_wide::*;
 
 #[cfg(test)]
 mod instruction_evaluation_tests;
\ No newline at end of file
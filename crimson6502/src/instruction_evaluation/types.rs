@@ -1,6 +1,7 @@
 use crate::cpu::CPUState;
 
-#[derive(Debug, PartialEq)]
+/// an executed instruction, as reported to `Observer::after_execute`
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct InstructionResult {
     pub state: CPUState,
     pub cycles: u8,
@@ -10,9 +11,9 @@ pub struct InstructionResult {
 impl InstructionResult {
     pub fn new(state: CPUState, cycles: u8, instruction_length: u8) -> InstructionResult {
         Self {
-            state: state,
-            cycles: cycles,
-            instruction_length: instruction_length,
+            state,
+            cycles,
+            instruction_length,
         }
     }
 }
\ No newline at end of file
@@ -1,13 +1,31 @@
 #[macro_use]
 mod macros;
 
+mod block_cache;
+mod breakpoint;
+mod bus;
 mod cpu;
+mod decode;
+mod error;
+mod history;
 mod instruction;
 mod memory;
+mod memory_map;
+mod observer;
+mod snapshot;
 mod instruction_evaluation;
 mod util;
+mod variant;
 
+pub use crate::breakpoint::{StopReason, WatchKind, Watchpoint};
+pub use crate::bus::{AccessKind, Bus, BusAccess};
+pub use crate::decode::DecodedOpcode;
+pub use crate::error::CpuError;
+pub use crate::instruction::{AddressingMode, Instruction, Mnemonic};
+pub use crate::instruction_evaluation::InstructionResult;
 pub use crate::memory::Memory;
-pub use crate::cpu::{CPU, CPUState, CPUStats};
-
-
+pub use crate::observer::{InterruptKind, Observer};
+pub use crate::memory_map::{BankMapping, MemoryMap, MemoryMapDescription, MemoryMapError, Region, RegionKind, RomWrite, RomWrites};
+pub use crate::snapshot::{Snapshot, SnapshotError, SNAPSHOT_JSON_FORMAT, SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+pub use crate::cpu::{CPU, CPUState, CPUStats, RunState, IRQ_VECTOR, NMI_VECTOR, RESET_VECTOR, COP_VECTOR, NATIVE_COP_VECTOR, NATIVE_BRK_VECTOR, NATIVE_NMI_VECTOR, NATIVE_IRQ_VECTOR};
+pub use crate::variant::CPUVariant;
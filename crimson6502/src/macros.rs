@@ -6,6 +6,9 @@ macro_rules! flag {
     (decimal) => {0b0000_1000};
     (brk) => {0b0001_0000};
     (unused) => {0b0010_0000};
+    // 65C816 native mode reuses bits 4 and 5: X and M select 8-bit index registers and accumulator
+    (index) => {0b0001_0000};
+    (memory) => {0b0010_0000};
     (overflow) => {0b0100_0000};
     (negative) => {0b1000_0000};
 }
\ No newline at end of file
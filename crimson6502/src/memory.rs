@@ -1,20 +1,68 @@
+/// size of one 64K bank
+const BANK_SIZE: usize = 0x10000;
+
 #[derive(Debug, PartialEq)]
 pub struct Memory {
-    data: [u8; 65536],
+    data: Vec<u8>,
 }
 
 impl Memory {
+    /// 64K of memory, enough for the 6502 address space
     pub fn new() -> Self {
+        Self::with_banks(1)
+    }
+
+    /// memory for the 24-bit address space of the 65C816, made of `banks` 64K banks.
+    /// addresses beyond the last bank mirror the ones below.
+    pub fn with_banks(banks: usize) -> Self {
         Self {
-            data: [0; 65536],
+            data: vec![0; banks.clamp(1, 256) * BANK_SIZE],
         }
     }
 
     pub fn read_byte(&self, addr: u16) -> u8 {
-        self.data[addr as usize]
+        self.read_byte_long(addr as u32)
     }
 
     pub fn write_byte(&mut self, addr: u16, value: u8) {
-        self.data[addr as usize] = value;
+        self.write_byte_long(addr as u32, value);
     }
-}
\ No newline at end of file
+
+    /// reads from a 24-bit address
+    pub fn read_byte_long(&self, addr: u32) -> u8 {
+        // the division is only needed for mirrored addresses
+        match self.data.get(addr as usize) {
+            Some(value) => *value,
+            None => self.data[addr as usize % self.data.len()],
+        }
+    }
+
+    /// writes to a 24-bit address
+    pub fn write_byte_long(&mut self, addr: u32, value: u8) {
+        let len: usize = self.data.len();
+        let index: usize = if (addr as usize) < len { addr as usize } else { addr as usize % len };
+        self.data[index] = value;
+    }
+
+    /// all banks, starting at address 0
+    pub fn contents(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// replaces all banks, which also changes the number of banks.
+    /// returns false, leaving the memory unchanged, unless contents is made of 1 to 256 banks.
+    pub fn set_contents(&mut self, contents: &[u8]) -> bool {
+        let banks: usize = contents.len() / BANK_SIZE;
+        if !contents.len().is_multiple_of(BANK_SIZE) || !(1..=256).contains(&banks) {
+            return false;
+        }
+        self.data = contents.to_vec();
+        true
+    }
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
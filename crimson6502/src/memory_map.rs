@@ -0,0 +1,511 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::bus::Bus;
+
+/// largest address of the 24-bit address space
+const ADDRESS_MASK: u32 = 0xFF_FFFF;
+/// the select register is a byte wide, so a banked region can reach at most this many banks
+const MAX_BANKS: u32 = 0x100;
+
+/// what a region of the address space is connected to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegionKind {
+    Ram,
+    /// read-only, see `RomWrites` for what happens on writes
+    Rom,
+    /// repeats the `size` bytes starting at `target` across the region.
+    /// the target has to lie inside a single RAM, ROM or banked region.
+    Mirror { target: u32, size: u32 },
+    /// nothing responds, reads return the last value seen on the data bus
+    OpenBus,
+    /// window onto one of `banks` read-only banks the size of the region.
+    /// writing n to `select` maps bank n modulo `banks`, bank 0 is mapped at start.
+    /// `banks` has to be between 1 and 256.
+    BankedRom { banks: u32, select: u32 },
+    /// writable counterpart of BankedRom
+    BankedRam { banks: u32, select: u32 },
+}
+
+/// an inclusive address range and what it is connected to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    pub start: u32,
+    pub end: u32,
+    #[serde(flatten)]
+    pub kind: RegionKind,
+}
+
+/// what happens when the CPU writes to ROM
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RomWrites {
+    /// the write is dropped, like on real hardware
+    #[default]
+    Ignore,
+    /// the write is dropped and recorded, see `MemoryMap::take_rom_writes`
+    Report,
+    /// the write is dropped and raises a bus fault, which stops the CPU with `CpuError::BusFault`
+    Fault,
+}
+
+/// a write that hit ROM while `RomWrites::Report` is configured
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RomWrite {
+    pub addr: u32,
+    pub value: u8,
+}
+
+/// declarative description of a machine's address space.
+/// addresses not covered by any region behave like `RegionKind::OpenBus`.
+///
+/// ```
+/// use crimson6502::{MemoryMap, MemoryMapDescription, RomWrites};
+///
+/// let description = MemoryMapDescription::new()
+///     .ram(0x0000, 0x07FF)
+///     .mirror(0x0800, 0x1FFF, 0x0000, 0x0800)
+///     .rom(0xC000, 0xFFFF)
+///     .rom_writes(RomWrites::Report);
+/// let memory_map = MemoryMap::new(&description).unwrap();
+/// ```
+///
+/// the same description as TOML:
+///
+/// ```toml
+/// rom_writes = "report"
+///
+/// [[region]]
+/// kind = "ram"
+/// start = 0x0000
+/// end = 0x07FF
+///
+/// [[region]]
+/// kind = "mirror"
+/// start = 0x0800
+/// end = 0x1FFF
+/// target = 0x0000
+/// size = 0x0800
+///
+/// [[region]]
+/// kind = "rom"
+/// start = 0xC000
+/// end = 0xFFFF
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryMapDescription {
+    #[serde(default)]
+    pub rom_writes: RomWrites,
+    #[serde(rename = "region", default)]
+    pub regions: Vec<Region>,
+}
+
+impl MemoryMapDescription {
+    /// an empty address space, every read returns open bus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// adds a region
+    pub fn region(mut self, start: u32, end: u32, kind: RegionKind) -> Self {
+        self.regions.push(Region { start, end, kind });
+        self
+    }
+
+    pub fn ram(self, start: u32, end: u32) -> Self {
+        self.region(start, end, RegionKind::Ram)
+    }
+
+    pub fn rom(self, start: u32, end: u32) -> Self {
+        self.region(start, end, RegionKind::Rom)
+    }
+
+    pub fn mirror(self, start: u32, end: u32, target: u32, size: u32) -> Self {
+        self.region(start, end, RegionKind::Mirror { target, size })
+    }
+
+    pub fn open_bus(self, start: u32, end: u32) -> Self {
+        self.region(start, end, RegionKind::OpenBus)
+    }
+
+    pub fn banked_rom(self, start: u32, end: u32, banks: u32, select: u32) -> Self {
+        self.region(start, end, RegionKind::BankedRom { banks, select })
+    }
+
+    pub fn banked_ram(self, start: u32, end: u32, banks: u32, select: u32) -> Self {
+        self.region(start, end, RegionKind::BankedRam { banks, select })
+    }
+
+    pub fn rom_writes(mut self, rom_writes: RomWrites) -> Self {
+        self.rom_writes = rom_writes;
+        self
+    }
+
+    /// parses a description written in TOML
+    pub fn from_toml(source: &str) -> Result<Self, MemoryMapError> {
+        toml::from_str(source).map_err(|error| MemoryMapError::Parse(error.to_string()))
+    }
+
+    /// writes the description as TOML
+    pub fn to_toml(&self) -> Result<String, MemoryMapError> {
+        toml::to_string(self).map_err(|error| MemoryMapError::Parse(error.to_string()))
+    }
+}
+
+/// why a memory map description was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryMapError {
+    /// the TOML source could not be parsed
+    Parse(String),
+    /// the region ends before it starts or lies outside the 24-bit address space
+    InvalidRange(Region),
+    /// two regions share addresses
+    Overlap(Region, Region),
+    /// a mirror is empty or does not point into a single RAM, ROM or banked region
+    InvalidMirror(Region),
+    /// a banked region has no banks, more than 256 banks or its select register lies outside the address space
+    InvalidBanks(Region),
+    /// the address does not belong to a banked region
+    NotBanked(u32),
+}
+
+impl fmt::Display for MemoryMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryMapError::Parse(message) => write!(f, "could not parse memory map: {}", message),
+            MemoryMapError::InvalidRange(region) => write!(f, "invalid region ${:06X}-${:06X}", region.start, region.end),
+            MemoryMapError::Overlap(first, second)
+                => write!(f, "region ${:06X}-${:06X} overlaps ${:06X}-${:06X}", first.start, first.end, second.start, second.end),
+            MemoryMapError::InvalidMirror(region)
+                => write!(f, "mirror ${:06X}-${:06X} does not point into RAM or ROM", region.start, region.end),
+            MemoryMapError::InvalidBanks(region)
+                => write!(f, "banked region ${:06X}-${:06X} needs between 1 and 256 banks and a select register", region.start, region.end),
+            MemoryMapError::NotBanked(addr) => write!(f, "${:06X} is not in a banked region", addr),
+        }
+    }
+}
+
+impl std::error::Error for MemoryMapError {}
+
+/// which bank a banked window currently maps, for debuggers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BankMapping {
+    pub start: u32,
+    pub end: u32,
+    pub bank: u32,
+    pub banks: u32,
+    pub select: u32,
+}
+
+/// a region together with its contents, empty for mirrors and open bus.
+/// banked regions hold every bank back to back.
+#[derive(Clone, Debug, PartialEq)]
+struct MappedRegion {
+    region: Region,
+    data: Vec<u8>,
+    /// currently mapped bank, always 0 for unbanked regions
+    bank: u32,
+}
+
+impl MappedRegion {
+    /// size of the window in the address space
+    fn window_size(&self) -> usize {
+        (self.region.end - self.region.start) as usize + 1
+    }
+
+    /// (banks, select register) of banked regions
+    fn banking(&self) -> Option<(u32, u32)> {
+        match self.region.kind {
+            RegionKind::BankedRom { banks, select } | RegionKind::BankedRam { banks, select } => Some((banks, select)),
+            _ => None,
+        }
+    }
+}
+
+/// address space built from a `MemoryMapDescription`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryMap {
+    regions: Vec<MappedRegion>,
+    rom_writes: RomWrites,
+    reported_rom_writes: Vec<RomWrite>,
+    /// ROM write waiting to be reported as a bus fault
+    fault: Option<u32>,
+    /// last value driven on the data bus, returned by open bus reads
+    data_bus: u8,
+}
+
+impl MemoryMap {
+    /// builds the address space, RAM and ROM start out zeroed
+    pub fn new(description: &MemoryMapDescription) -> Result<Self, MemoryMapError> {
+        let mut regions: Vec<Region> = description.regions.clone();
+        regions.sort_by_key(|region| region.start);
+
+        for region in regions.iter() {
+            if region.start > region.end || region.end > ADDRESS_MASK {
+                return Err(MemoryMapError::InvalidRange(*region));
+            }
+        }
+        for pair in regions.windows(2) {
+            if pair[1].start <= pair[0].end {
+                return Err(MemoryMapError::Overlap(pair[0], pair[1]));
+            }
+        }
+        for region in regions.iter() {
+            match region.kind {
+                RegionKind::Mirror { target, size } => {
+                    let backed: bool = size > 0 && regions.iter().any(|other| {
+                        !matches!(other.kind, RegionKind::Mirror { .. } | RegionKind::OpenBus)
+                            && other.start <= target
+                            && target as u64 + size as u64 - 1 <= other.end as u64
+                    });
+                    if !backed {
+                        return Err(MemoryMapError::InvalidMirror(*region));
+                    }
+                },
+                RegionKind::BankedRom { banks, select } | RegionKind::BankedRam { banks, select }
+                    if banks == 0
+                        || banks > MAX_BANKS
+                        || select > ADDRESS_MASK
+                        || ((region.end - region.start) as usize + 1).checked_mul(banks as usize).is_none()
+                    => return Err(MemoryMapError::InvalidBanks(*region)),
+                _ => (),
+            }
+        }
+
+        let regions: Vec<MappedRegion> = regions
+            .into_iter()
+            .map(|region| {
+                let window_size: usize = (region.end - region.start) as usize + 1;
+                let size: usize = match region.kind {
+                    RegionKind::Ram | RegionKind::Rom => window_size,
+                    RegionKind::BankedRom { banks, .. } | RegionKind::BankedRam { banks, .. } => window_size * banks as usize,
+                    RegionKind::Mirror { .. } | RegionKind::OpenBus => 0,
+                };
+                MappedRegion { region, data: vec![0; size], bank: 0 }
+            })
+            .collect();
+
+        Ok(Self {
+            regions,
+            rom_writes: description.rom_writes,
+            reported_rom_writes: Vec::new(),
+            fault: None,
+            data_bus: 0,
+        })
+    }
+
+    /// parses a TOML description and builds the address space
+    pub fn from_toml(source: &str) -> Result<Self, MemoryMapError> {
+        Self::new(&MemoryMapDescription::from_toml(source)?)
+    }
+
+    /// copies `bytes` into RAM or ROM starting at `addr`, e.g. to load firmware.
+    /// ROM is written as well, bytes landing on open bus are dropped.
+    /// banked regions receive the bytes in their currently mapped bank.
+    pub fn load(&mut self, addr: u32, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            if let Some((index, offset)) = self.locate(addr.wrapping_add(i as u32)) {
+                self.regions[index].data[offset] = *byte;
+            }
+        }
+    }
+
+    /// copies `bytes` into `bank` of the banked region containing `addr`, starting at the offset of
+    /// `addr` in the window. bytes past the end of the bank continue in the following banks, so a
+    /// whole banked image can be loaded at once. bytes past the last bank are dropped.
+    pub fn load_bank(&mut self, addr: u32, bank: u32, bytes: &[u8]) -> Result<(), MemoryMapError> {
+        let addr: u32 = addr & ADDRESS_MASK;
+        let index: usize = self.banked_region(addr)?;
+        let mapped: &mut MappedRegion = &mut self.regions[index];
+        let start: usize = bank as usize * mapped.window_size() + (addr - mapped.region.start) as usize;
+        for (i, byte) in bytes.iter().enumerate() {
+            if let Some(cell) = mapped.data.get_mut(start + i) {
+                *cell = *byte;
+            }
+        }
+        Ok(())
+    }
+
+    /// maps `bank` (modulo the bank count) into the banked region containing `addr`,
+    /// same as writing to its select register
+    pub fn select_bank(&mut self, addr: u32, bank: u32) -> Result<(), MemoryMapError> {
+        let index: usize = self.banked_region(addr & ADDRESS_MASK)?;
+        let mapped: &mut MappedRegion = &mut self.regions[index];
+        if let Some((banks, _)) = mapped.banking() {
+            mapped.bank = bank % banks;
+        }
+        Ok(())
+    }
+
+    /// the currently mapped bank of every banked region, in address order
+    pub fn mapped_banks(&self) -> Vec<BankMapping> {
+        self.regions
+            .iter()
+            .filter_map(|mapped| {
+                mapped.banking().map(|(banks, select)| BankMapping {
+                    start: mapped.region.start,
+                    end: mapped.region.end,
+                    bank: mapped.bank,
+                    banks,
+                    select,
+                })
+            })
+            .collect()
+    }
+
+    /// index of the banked region containing addr
+    fn banked_region(&self, addr: u32) -> Result<usize, MemoryMapError> {
+        self.find(addr)
+            .filter(|index| self.regions[*index].banking().is_some())
+            .ok_or(MemoryMapError::NotBanked(addr))
+    }
+
+    /// latches the bank of every window selected by addr.
+    /// returns whether addr is a select register, those writes go nowhere else.
+    fn latch_bank(&mut self, addr: u32, value: u8) -> bool {
+        let mut latched: bool = false;
+        for mapped in self.regions.iter_mut() {
+            if let Some((banks, select)) = mapped.banking() && select == addr {
+                mapped.bank = value as u32 % banks;
+                latched = true;
+            }
+        }
+        latched
+    }
+
+    /// returns and clears the writes that hit ROM since the last call.
+    /// always empty unless `RomWrites::Report` is configured.
+    pub fn take_rom_writes(&mut self) -> Vec<RomWrite> {
+        std::mem::take(&mut self.reported_rom_writes)
+    }
+
+    /// the region an address belongs to, None for addresses outside every region
+    pub fn region_at(&self, addr: u32) -> Option<Region> {
+        self.find(addr & ADDRESS_MASK).map(|index| self.regions[index].region)
+    }
+
+    /// index of the region containing addr
+    fn find(&self, addr: u32) -> Option<usize> {
+        self.regions
+            .iter()
+            .position(|mapped| mapped.region.start <= addr && addr <= mapped.region.end)
+    }
+
+    /// offset of addr into the data of a backed region, taking the mapped bank into account
+    fn offset(&self, index: usize, addr: u32) -> usize {
+        let mapped: &MappedRegion = &self.regions[index];
+        mapped.bank as usize * mapped.window_size() + (addr - mapped.region.start) as usize
+    }
+
+    /// resolves addr to a region index and offset into its data, following mirrors.
+    /// None for open bus.
+    fn locate(&self, addr: u32) -> Option<(usize, usize)> {
+        let addr: u32 = addr & ADDRESS_MASK;
+        let index: usize = self.find(addr)?;
+        let region: Region = self.regions[index].region;
+        match region.kind {
+            RegionKind::Mirror { target, size } => {
+                // validated to land inside a single backed region
+                let target_addr: u32 = target + (addr - region.start) % size;
+                let target_index: usize = self.find(target_addr)?;
+                Some((target_index, self.offset(target_index, target_addr)))
+            },
+            RegionKind::OpenBus => None,
+            _ => Some((index, self.offset(index, addr))),
+        }
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, addr: u32) -> u8 {
+        if let Some((index, offset)) = self.locate(addr) {
+            self.data_bus = self.regions[index].data[offset];
+        }
+        self.data_bus
+    }
+
+    fn write(&mut self, addr: u32, value: u8) {
+        self.data_bus = value;
+        if self.latch_bank(addr & ADDRESS_MASK, value) {
+            return;
+        }
+        if let Some((index, offset)) = self.locate(addr) {
+            let mapped: &mut MappedRegion = &mut self.regions[index];
+            match mapped.region.kind {
+                RegionKind::Rom | RegionKind::BankedRom { .. } => match self.rom_writes {
+                    RomWrites::Ignore => (),
+                    RomWrites::Report => self.reported_rom_writes.push(RomWrite { addr: addr & ADDRESS_MASK, value }),
+                    RomWrites::Fault => self.fault = Some(addr & ADDRESS_MASK),
+                },
+                _ => mapped.data[offset] = value,
+            }
+        }
+    }
+
+    fn peek(&self, addr: u32) -> u8 {
+        match self.locate(addr) {
+            Some((index, offset)) => self.regions[index].data[offset],
+            None => self.data_bus,
+        }
+    }
+
+    /// stores into RAM and ROM alike, select registers are not latched
+    fn poke(&mut self, addr: u32, value: u8) {
+        if let Some((index, offset)) = self.locate(addr) {
+            self.regions[index].data[offset] = value;
+        }
+    }
+
+    /// the mapped bank of every region in address order
+    fn latches(&self) -> Vec<u32> {
+        self.regions.iter().map(|mapped| mapped.bank).collect()
+    }
+
+    fn restore_latches(&mut self, latches: &[u32]) {
+        for (mapped, bank) in self.regions.iter_mut().zip(latches) {
+            mapped.bank = *bank;
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<u32> {
+        self.fault.take()
+    }
+
+    /// the data bus latch, then the mapped bank (u32, little endian) and data of every region
+    /// in address order
+    fn save_contents(&self) -> Option<Vec<u8>> {
+        let mut contents: Vec<u8> = vec![self.data_bus];
+        for mapped in self.regions.iter() {
+            contents.extend_from_slice(&mapped.bank.to_le_bytes());
+            contents.extend_from_slice(&mapped.data);
+        }
+        Some(contents)
+    }
+
+    /// contents must come from a memory map with the same regions
+    fn load_contents(&mut self, contents: &[u8]) -> bool {
+        let expected_len: usize = 1 + self.regions.iter().map(|mapped| 4 + mapped.data.len()).sum::<usize>();
+        if contents.len() != expected_len {
+            return false;
+        }
+        let banks_valid: bool = self.regions.iter().scan(1, |position, mapped| {
+            let bank: u32 = u32::from_le_bytes(contents[*position..*position + 4].try_into().unwrap());
+            *position += 4 + mapped.data.len();
+            Some(bank == 0 || mapped.banking().is_some_and(|(banks, _)| bank < banks))
+        }).all(|valid| valid);
+        if !banks_valid {
+            return false;
+        }
+
+        self.data_bus = contents[0];
+        let mut position: usize = 1;
+        for mapped in self.regions.iter_mut() {
+            mapped.bank = u32::from_le_bytes(contents[position..position + 4].try_into().unwrap());
+            position += 4;
+            let len: usize = mapped.data.len();
+            mapped.data.copy_from_slice(&contents[position..position + len]);
+            position += len;
+        }
+        true
+    }
+}
@@ -0,0 +1,60 @@
+use crate::cpu::CPUState;
+use crate::instruction::Instruction;
+use crate::instruction_evaluation::InstructionResult;
+
+/// what started an interrupt sequence
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterruptKind {
+    Irq,
+    Nmi,
+    Brk,
+    /// 65C816 COP instruction
+    Cop,
+}
+
+/// receives execution events of a CPU, e.g. for tracers, profilers and coverage tools.
+/// every callback does nothing by default. the CPU is generic over its observer, so the default
+/// `()` observer costs nothing.
+/// when the CPU is stepped by tick, reads and writes are reported in the cycle they happen,
+/// after_execute and on_interrupt when the instruction completes.
+pub trait Observer {
+    /// before the opcode at the 24-bit addr is fetched
+    fn before_fetch(&mut self, _addr: u32, _state: &CPUState) {}
+
+    /// after an instruction executed, `result.state` holds the registers it produced
+    fn after_execute(&mut self, _instruction: &Instruction, _result: &InstructionResult) {}
+
+    /// after every read on the bus, including dummy reads
+    fn on_read(&mut self, _addr: u32, _value: u8) {}
+
+    /// after every write on the bus, including dummy writes
+    fn on_write(&mut self, _addr: u32, _value: u8) {}
+
+    /// when an interrupt sequence starts, `state` holds the registers about to be pushed
+    fn on_interrupt(&mut self, _kind: InterruptKind, _state: &CPUState) {}
+}
+
+impl Observer for () {}
+
+/// lets the observer be chosen at runtime
+impl<O: Observer + ?Sized> Observer for Box<O> {
+    fn before_fetch(&mut self, addr: u32, state: &CPUState) {
+        (**self).before_fetch(addr, state);
+    }
+
+    fn after_execute(&mut self, instruction: &Instruction, result: &InstructionResult) {
+        (**self).after_execute(instruction, result);
+    }
+
+    fn on_read(&mut self, addr: u32, value: u8) {
+        (**self).on_read(addr, value);
+    }
+
+    fn on_write(&mut self, addr: u32, value: u8) {
+        (**self).on_write(addr, value);
+    }
+
+    fn on_interrupt(&mut self, kind: InterruptKind, state: &CPUState) {
+        (**self).on_interrupt(kind, state);
+    }
+}
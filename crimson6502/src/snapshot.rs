@@ -0,0 +1,299 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::cpu::{CPUState, CPUStats, RunState};
+use crate::variant::CPUVariant;
+
+/// first bytes of every binary snapshot
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"CRIMSNAP";
+/// format identifier of JSON snapshots, the counterpart of the magic
+pub const SNAPSHOT_JSON_FORMAT: &str = "crimson6502 snapshot";
+/// snapshot layout version, bumped whenever the layout changes.
+/// snapshots of other versions are rejected instead of being misread.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// bytes per row of the bus contents in JSON snapshots
+const JSON_ROW_SIZE: usize = 32;
+
+/// the complete machine at an instruction boundary: CPU, interrupt lines and bus contents.
+/// breakpoints and the bus log belong to the debugger and are not part of it.
+///
+/// binary layout, little endian: magic, version (u16), variant (u8), A X Y S (u8), PC (u16),
+/// P B XH YH SH (u8), D (u16), DBR PBR E (u8), total cycles and instructions (u64), run state (u8),
+/// flags (u8: page crossed, IRQ line, NMI line, NMI pending, undocumented opcodes, dummy accesses),
+/// bus contents length (u32) and bus contents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub variant: CPUVariant,
+    pub state: CPUState,
+    pub stats: CPUStats,
+    pub run_state: RunState,
+    pub page_crossed: bool,
+    pub irq_line: bool,
+    pub nmi_line: bool,
+    pub nmi_pending: bool,
+    pub undocumented_opcodes: bool,
+    pub dummy_accesses: bool,
+    /// bus contents as saved by `Bus::save_contents`, hex rows in JSON
+    #[serde(with = "hex_rows")]
+    pub bus: Vec<u8>,
+}
+
+/// why a snapshot could not be taken, read or restored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// the data does not start with the snapshot magic or format identifier
+    NotASnapshot,
+    /// the snapshot was written with another layout version
+    UnsupportedVersion(u16),
+    /// the data ends before the snapshot does
+    Truncated,
+    /// a field holds a value no snapshot contains
+    Corrupt(String),
+    /// the bus cannot be saved, or the saved contents do not fit it
+    UnsupportedBus,
+    /// tick stopped in the middle of an instruction, snapshots are taken between instructions
+    InstructionInProgress,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::NotASnapshot => write!(f, "not a snapshot"),
+            SnapshotError::UnsupportedVersion(version) => write!(f, "unsupported snapshot version {}, expected {}", version, SNAPSHOT_VERSION),
+            SnapshotError::Truncated => write!(f, "snapshot is truncated"),
+            SnapshotError::Corrupt(message) => write!(f, "corrupt snapshot: {}", message),
+            SnapshotError::UnsupportedBus => write!(f, "bus contents cannot be saved or do not fit the bus"),
+            SnapshotError::InstructionInProgress => write!(f, "an instruction is in progress"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// header every JSON snapshot starts with, checked before the rest is parsed
+#[derive(Deserialize)]
+struct JsonHeader {
+    format: String,
+    version: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshot {
+    format: String,
+    version: u16,
+    #[serde(flatten)]
+    snapshot: Snapshot,
+}
+
+impl Snapshot {
+    /// encodes the snapshot in the binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(64 + self.bus.len());
+        bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.push(variant_to_byte(self.variant));
+
+        let state: &CPUState = &self.state;
+        bytes.extend_from_slice(&[state.a, state.x, state.y, state.s]);
+        bytes.extend_from_slice(&state.pc.to_le_bytes());
+        bytes.extend_from_slice(&[state.sr, state.b, state.xh, state.yh, state.sh]);
+        bytes.extend_from_slice(&state.d.to_le_bytes());
+        bytes.extend_from_slice(&[state.dbr, state.pbr, state.e as u8]);
+
+        bytes.extend_from_slice(&(self.stats.total_cycles() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.stats.instructions() as u64).to_le_bytes());
+        bytes.push(run_state_to_byte(self.run_state));
+        let flags: [bool; 6] = [self.page_crossed, self.irq_line, self.nmi_line, self.nmi_pending, self.undocumented_opcodes, self.dummy_accesses];
+        bytes.push(flags.iter().enumerate().fold(0, |byte, (bit, flag)| byte | ((*flag as u8) << bit)));
+
+        bytes.extend_from_slice(&(self.bus.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.bus);
+        bytes
+    }
+
+    /// decodes a snapshot in the binary format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Snapshot, SnapshotError> {
+        if !bytes.starts_with(&SNAPSHOT_MAGIC) {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let mut reader: Reader = Reader { bytes, position: SNAPSHOT_MAGIC.len() };
+        let version: u16 = reader.u16()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let variant: CPUVariant = variant_from_byte(reader.u8()?)?;
+
+        let mut state: CPUState = CPUState::new();
+        state.a = reader.u8()?;
+        state.x = reader.u8()?;
+        state.y = reader.u8()?;
+        state.s = reader.u8()?;
+        state.pc = reader.u16()?;
+        state.sr = reader.u8()?;
+        state.b = reader.u8()?;
+        state.xh = reader.u8()?;
+        state.yh = reader.u8()?;
+        state.sh = reader.u8()?;
+        state.d = reader.u16()?;
+        state.dbr = reader.u8()?;
+        state.pbr = reader.u8()?;
+        state.e = reader.u8()? != 0;
+
+        let total_cycles: usize = reader.u64()? as usize;
+        let instructions: usize = reader.u64()? as usize;
+        let run_state: RunState = run_state_from_byte(reader.u8()?)?;
+        let flags: u8 = reader.u8()?;
+        let flag = |bit: u8| flags & (1 << bit) != 0;
+
+        let bus_len: usize = reader.u32()? as usize;
+        let bus: Vec<u8> = reader.bytes(bus_len)?.to_vec();
+        if reader.position != bytes.len() {
+            return Err(SnapshotError::Corrupt(String::from("trailing bytes")));
+        }
+
+        Ok(Snapshot {
+            variant,
+            state,
+            stats: CPUStats::with_counts(total_cycles, instructions),
+            run_state,
+            page_crossed: flag(0),
+            irq_line: flag(1),
+            nmi_line: flag(2),
+            nmi_pending: flag(3),
+            undocumented_opcodes: flag(4),
+            dummy_accesses: flag(5),
+            bus,
+        })
+    }
+
+    /// encodes the snapshot as pretty printed JSON
+    pub fn to_json(&self) -> String {
+        let json_snapshot: JsonSnapshot = JsonSnapshot {
+            format: String::from(SNAPSHOT_JSON_FORMAT),
+            version: SNAPSHOT_VERSION,
+            snapshot: self.clone(),
+        };
+        serde_json::to_string_pretty(&json_snapshot).expect("snapshots always serialize")
+    }
+
+    /// decodes a snapshot from JSON
+    pub fn from_json(json: &str) -> Result<Snapshot, SnapshotError> {
+        let header: JsonHeader = serde_json::from_str(json).map_err(|_| SnapshotError::NotASnapshot)?;
+        if header.format != SNAPSHOT_JSON_FORMAT {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+        let json_snapshot: JsonSnapshot = serde_json::from_str(json).map_err(|error| SnapshotError::Corrupt(error.to_string()))?;
+        Ok(json_snapshot.snapshot)
+    }
+}
+
+/// cursor over the binary format
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        let end: usize = self.position.checked_add(len).ok_or(SnapshotError::Truncated)?;
+        let bytes: &'a [u8] = self.bytes.get(self.position..end).ok_or(SnapshotError::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array: [u8; N] = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+}
+
+fn variant_to_byte(variant: CPUVariant) -> u8 {
+    match variant {
+        CPUVariant::Nmos6502 => 0,
+        CPUVariant::Cmos65C02 => 1,
+        CPUVariant::Wdc65C02 => 2,
+        CPUVariant::Wdc65C816 => 3,
+        CPUVariant::Ricoh2A03 => 4,
+    }
+}
+
+fn variant_from_byte(byte: u8) -> Result<CPUVariant, SnapshotError> {
+    let variant: CPUVariant = match byte {
+        0 => CPUVariant::Nmos6502,
+        1 => CPUVariant::Cmos65C02,
+        2 => CPUVariant::Wdc65C02,
+        3 => CPUVariant::Wdc65C816,
+        4 => CPUVariant::Ricoh2A03,
+        _ => return Err(SnapshotError::Corrupt(format!("unknown variant {}", byte))),
+    };
+    Ok(variant)
+}
+
+fn run_state_to_byte(run_state: RunState) -> u8 {
+    match run_state {
+        RunState::Running => 0,
+        RunState::Jammed => 1,
+        RunState::Waiting => 2,
+        RunState::Stopped => 3,
+    }
+}
+
+fn run_state_from_byte(byte: u8) -> Result<RunState, SnapshotError> {
+    let run_state: RunState = match byte {
+        0 => RunState::Running,
+        1 => RunState::Jammed,
+        2 => RunState::Waiting,
+        3 => RunState::Stopped,
+        _ => return Err(SnapshotError::Corrupt(format!("unknown run state {}", byte))),
+    };
+    Ok(run_state)
+}
+
+/// bus contents as rows of hex digits, which keeps JSON snapshots readable and diffable
+mod hex_rows {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use super::JSON_ROW_SIZE;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(bytes.chunks(JSON_ROW_SIZE).map(|row| {
+            row.iter().map(|byte| format!("{:02X}", byte)).collect::<String>()
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let rows: Vec<String> = Vec::deserialize(deserializer)?;
+        let mut bytes: Vec<u8> = Vec::with_capacity(rows.len() * JSON_ROW_SIZE);
+        for row in rows {
+            if row.len() % 2 != 0 || !row.is_ascii() {
+                return Err(D::Error::custom(format!("invalid hex row {:?}", row)));
+            }
+            for i in (0..row.len()).step_by(2) {
+                let byte: u8 = u8::from_str_radix(&row[i..i + 2], 16)
+                    .map_err(|_| D::Error::custom(format!("invalid hex row {:?}", row)))?;
+                bytes.push(byte);
+            }
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// the 6502 family member being emulated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CPUVariant {
+    /// original NMOS 6502, including undocumented opcodes, NMOS decimal mode and the JMP ($xxFF) bug
+    #[default]
+    Nmos6502,
+    /// CMOS 65C02: new instructions and (zp) addressing, undefined opcodes are NOPs,
+    /// valid N/Z in decimal mode and no JMP ($xxFF) bug
+    Cmos65C02,
+    /// WDC W65C02S: 65C02 with RMB/SMB, BBR/BBS, WAI and STP
+    Wdc65C02,
+    /// WDC 65C816: 16-bit registers, 24-bit addresses and native mode.
+    /// starts in emulation mode, which behaves like a 65C02
+    Wdc65C816,
+    /// Ricoh 2A03 (NES): NMOS core without decimal mode, D can be set but ADC/SBC ignore it
+    Ricoh2A03,
+}
+
+impl CPUVariant {
+    /// whether this variant is a CMOS part
+    pub const fn is_cmos(&self) -> bool {
+        matches!(self, CPUVariant::Cmos65C02 | CPUVariant::Wdc65C02 | CPUVariant::Wdc65C816)
+    }
+
+    /// whether this variant has the 65C816 registers and 24-bit address space
+    pub const fn is_65816(&self) -> bool {
+        matches!(self, CPUVariant::Wdc65C816)
+    }
+
+    /// whether ADC/SBC honour the D flag
+    pub const fn has_decimal_mode(&self) -> bool {
+        !matches!(self, CPUVariant::Ricoh2A03)
+    }
+}
@@ -1,14 +1,21 @@
 use std::io;
-use std::io::{Write, Read};
+use std::io::Write;
 use std::fs;
 use std::time::Instant;
-use crimson6502::{CPU, Memory, CPUState};
+use crimson6502::{CPU, Memory, CPUState, RunState, SnapshotError, StopReason};
 use crate::command::{CommandResult, Signal};
 use crate::command_parser::CommandParser;
+use crate::tracer::Tracer;
+
+/// instructions the REPL can step back through
+const HISTORY_CAPACITY: usize = 10_000;
+
+/// the REPL's CPU, traced by a Tracer
+type SessionCPU = CPU<Memory, Tracer>;
 
 pub struct CLISession {
     pub quit: bool,
-    cpu: Option<CPU>,
+    cpu: Option<SessionCPU>,
     memory: Option<Memory>,
     file_contents: Option<Vec<u8>>,
 }
@@ -37,12 +44,12 @@ impl CLISession {
         io::stdout().flush().unwrap();
 
         let mut input: String = String::new();
-        if let Err(_) = io::stdin().read_line(&mut input) {
+        if io::stdin().read_line(&mut input).is_err() {
             return None;
         }
         let input = input.trim().to_string();
 
-        if input.len() == 0 {
+        if input.is_empty() {
             return None;
         }
 
@@ -54,23 +61,28 @@ impl CLISession {
     }
 
     fn read_file(&mut self, path: &str) {
-        if let Ok(mut file) = fs::File::open(path.to_string() + ".txt") {
-            let mut buffer =  String::new();
-            let _ = file.read_to_string(&mut buffer);
-            let x = buffer.split_whitespace().map(|x: &str| u8::from_str_radix(x, 16)).collect();
-            let vec: Vec<u8> = match x {
-                Ok(v) => v,
-                Err(err) => panic!("{:?}", err),
-            };
-            self.file_contents = Some(vec);
-        } else {
-            panic!("failed to open file: {}", path);
+        let file_name: String = path.to_string() + ".txt";
+        let buffer: String = match fs::read_to_string(&file_name) {
+            Ok(buffer) => buffer,
+            Err(error) => {
+                println!("Failed to open {}: {}", file_name, error);
+                return;
+            },
+        };
+        let parsed: Result<Vec<u8>, _> = buffer.split_whitespace().map(|x: &str| u8::from_str_radix(x, 16)).collect();
+        match parsed {
+            Ok(vec) => self.file_contents = Some(vec),
+            Err(error) => println!("Failed to parse {}: {}", file_name, error),
         }
     }
 
     fn run_file(&mut self) {
-        for (i, value) in self.file_contents.clone().unwrap().into_iter().enumerate() {
-            self.cpu.as_mut().unwrap().write_byte(i as u16, value);
+        let (Some(file_contents), Some(cpu)) = (self.file_contents.as_ref(), self.cpu.as_mut()) else {
+            println!("Open a file and initialize the CPU first.");
+            return;
+        };
+        for (i, value) in file_contents.iter().enumerate() {
+            cpu.write_byte(i as u16, *value);
         }
     }
 
@@ -90,32 +102,155 @@ impl CLISession {
                 Signal::CPUStep(_) 
                     => println!("CPU and/or Memory have not been initialized."),
                 Signal::InitCPU
-                    => self.cpu = Some(CPU::new(CPUState::new(), Memory::new())),
+                    => self.cpu = Some(Self::new_cpu()),
                 Signal::InitMemory
                     => self.memory = Some(Memory::new()),
                 Signal::InitAll
                     => {
-                        self.cpu = Some(CPU::new(CPUState::new(), Memory::new()));
+                        self.cpu = Some(Self::new_cpu());
                         self.memory = Some(Memory::new());
                     },
-                Signal::WriteMemory(addr, value) if self.memory.is_some() 
+                Signal::WriteMemory(addr, value) if self.cpu_ready()
                     => self.cpu.as_mut().unwrap().write_byte(addr, value),
                 Signal::WriteMemory(_, _)
-                    => println!("Cannot write to Memory, it has not been initialized."),
+                    => println!("Cannot write to Memory, CPU and/or Memory have not been initialized."),
                 Signal::FileOpen(path)
                     => self.read_file(&path),
                 Signal::FileRun
                     => self.run_file(),
+                Signal::SaveState(path) if self.cpu.is_some()
+                    => self.save_state(&path),
+                Signal::SaveState(_)
+                    => println!("Cannot save state, CPU has not been initialized."),
+                Signal::LoadState(path)
+                    => self.load_state(&path),
+                Signal::StepBack(n) if self.cpu_ready()
+                    => self.execute_step_back(n),
+                Signal::ReverseContinue(addr) if self.cpu_ready()
+                    => self.execute_reverse_continue(addr),
+                Signal::StepBack(_) | Signal::ReverseContinue(_)
+                    => println!("CPU and/or Memory have not been initialized."),
+                Signal::Trace(enabled) if self.cpu.is_some()
+                    => self.cpu.as_mut().unwrap().observer_mut().enabled = enabled,
+                Signal::Trace(_)
+                    => println!("CPU has not been initialized."),
+                Signal::AddBreakpoint(addr) if self.cpu.is_some()
+                    => self.cpu.as_mut().unwrap().add_breakpoint(addr),
+                Signal::RemoveBreakpoint(addr) if self.cpu.is_some()
+                    => if !self.cpu.as_mut().unwrap().remove_breakpoint(addr) {
+                        println!("No breakpoint at ${:06X}", addr);
+                    },
+                Signal::AddWatchpoint(watchpoint) if self.cpu.is_some()
+                    => self.cpu.as_mut().unwrap().add_watchpoint(watchpoint),
+                Signal::RemoveWatchpoint(watchpoint) if self.cpu.is_some()
+                    => if !self.cpu.as_mut().unwrap().remove_watchpoint(&watchpoint) {
+                        println!("No such watchpoint");
+                    },
+                Signal::AddBreakpoint(_) | Signal::RemoveBreakpoint(_)
+                | Signal::AddWatchpoint(_) | Signal::RemoveWatchpoint(_)
+                    => println!("CPU has not been initialized."),
+                Signal::Continue(cycles) if self.cpu_ready()
+                    => {
+                        let now = Instant::now();
+                        self.execute_continue(cycles);
+                        println!("Elapsed: {:?}", now.elapsed());
+                    },
+                Signal::Continue(_)
+                    => println!("CPU and/or Memory have not been initialized."),
             }
         }
     }
 
+    /// writes a snapshot of the CPU and memory, as JSON if the path ends in .json
+    fn save_state(&mut self, path: &str) {
+        let cpu: &SessionCPU = self.cpu.as_ref().unwrap();
+        let saved: Result<Vec<u8>, SnapshotError> = if path.ends_with(".json") {
+            cpu.save_state_json().map(String::into_bytes)
+        } else {
+            cpu.save_state()
+        };
+        match saved {
+            Ok(bytes) => match fs::write(path, bytes) {
+                Ok(()) => println!("Saved state to {}", path),
+                Err(error) => println!("Failed to write {}: {}", path, error),
+            },
+            Err(error) => println!("Failed to save state: {}", error),
+        }
+    }
+
+    /// restores a snapshot written by save, initializing the CPU and memory if necessary
+    fn load_state(&mut self, path: &str) {
+        let bytes: Vec<u8> = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                println!("Failed to read {}: {}", path, error);
+                return;
+            },
+        };
+        let cpu: &mut SessionCPU = self.cpu.get_or_insert_with(Self::new_cpu);
+        let loaded: Result<(), SnapshotError> = if path.ends_with(".json") {
+            match String::from_utf8(bytes) {
+                Ok(json) => cpu.load_state_json(&json),
+                Err(_) => Err(SnapshotError::NotASnapshot),
+            }
+        } else {
+            cpu.load_state(&bytes)
+        };
+        match loaded {
+            Ok(()) => {
+                self.memory.get_or_insert_with(Memory::new);
+                println!("Loaded state from {}", path);
+            },
+            Err(error) => println!("Failed to load state: {}", error),
+        }
+    }
+
+    fn new_cpu() -> SessionCPU {
+        let mut cpu: SessionCPU = CPU::new(CPUState::new(), Memory::new()).with_observer(Tracer::new());
+        cpu.set_history_capacity(HISTORY_CAPACITY);
+        cpu
+    }
+
+    fn execute_step_back(&mut self, n: usize) {
+        let cpu: &mut SessionCPU = self.cpu.as_mut().unwrap();
+        let undone: usize = cpu.step_back(n);
+        println!("Stepped back {} instruction(s), PC = ${:06X}", undone, cpu.program_counter());
+    }
+
+    fn execute_reverse_continue(&mut self, addr: u32) {
+        let cpu: &mut SessionCPU = self.cpu.as_mut().unwrap();
+        match cpu.step_back_until(addr) {
+            Some(undone) => println!("Stepped back {} instruction(s) to PC = ${:06X}", undone, addr),
+            None => println!("History exhausted before reaching ${:06X}, PC = ${:06X}", addr, cpu.program_counter()),
+        }
+    }
+
     fn execute_cpu_step(&mut self, n: isize) {
+        let cpu: &mut SessionCPU = self.cpu.as_mut().unwrap();
         for _ in 0..n {
-            self.cpu
-                .as_mut()
-                .unwrap()
-                .run();
+            if let Err(error) = cpu.run() {
+                println!("CPU error: {}", error);
+                break;
+            }
+            // a waiting CPU would only spin until an interrupt arrives, report it instead
+            if cpu.run_state() == RunState::Waiting {
+                println!("CPU waiting for an interrupt");
+                break;
+            }
+        }
+    }
+
+    fn execute_continue(&mut self, cycles: usize) {
+        let cpu: &mut SessionCPU = self.cpu.as_mut().unwrap();
+        match cpu.run_until(cycles) {
+            Ok(StopReason::Breakpoint { addr }) => println!("Breakpoint at ${:06X}", addr),
+            Ok(StopReason::Watchpoint { access }) => println!("Watchpoint: {:?} of ${:02X} at ${:06X}, PC = ${:06X}",
+                access.kind, access.data, access.addr, cpu.program_counter()),
+            Ok(StopReason::CycleBudget) => println!("Ran {} cycle(s), PC = ${:06X}", cycles, cpu.program_counter()),
+            Ok(StopReason::IllegalOpcode { opcode, addr }) => println!("Illegal opcode ${:02X} at ${:06X}", opcode, addr),
+            Ok(StopReason::Halted { run_state, addr }) => println!("CPU halted ({:?}) at ${:06X}", run_state, addr),
+            Ok(StopReason::Waiting { addr }) => println!("CPU waiting for an interrupt at ${:06X}", addr),
+            Err(error) => println!("CPU error: {}", error),
         }
     }
 
@@ -1,3 +1,5 @@
+use crimson6502::Watchpoint;
+
 #[derive(Clone)]
 pub enum CommandResult {
     Signal(Signal),
@@ -15,4 +17,14 @@ pub enum Signal {
     WriteMemory(u16, u8),
     FileOpen(String),
     FileRun,
+    SaveState(String),
+    LoadState(String),
+    StepBack(usize),
+    ReverseContinue(u32),
+    Trace(bool),
+    AddBreakpoint(u32),
+    RemoveBreakpoint(u32),
+    AddWatchpoint(Watchpoint),
+    RemoveWatchpoint(Watchpoint),
+    Continue(usize),
 }
\ No newline at end of file
@@ -1,9 +1,10 @@
 use crate::command::CommandResult;
 use crate::command::Signal;
+use crimson6502::{WatchKind, Watchpoint};
 
-const ERR_ARG_COUNT_STR: &'static str = "Invalid number of arguments received for command: ";
-const ERR_INVALID_ARG_STR: &'static str = "Invalid argument received: ";
-const ERR_UNKNOWN_STR: &'static str = "Unknown command: ";
+const ERR_ARG_COUNT_STR: &str = "Invalid number of arguments received for command: ";
+const ERR_INVALID_ARG_STR: &str = "Invalid argument received: ";
+const ERR_UNKNOWN_STR: &str = "Unknown command: ";
 
 pub struct CommandParser {}
 
@@ -60,7 +61,127 @@ impl CommandParser {
             },
             "file" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "file"),
             //----------------------------------------------------------------------------------------------
+            "save" if input_slice.len() == 2 => CommandResult::Signal(Signal::SaveState(input_slice[1].clone())),
+            "save" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "save"),
+            "load" if input_slice.len() == 2 => CommandResult::Signal(Signal::LoadState(input_slice[1].clone())),
+            "load" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "load"),
+            //----------------------------------------------------------------------------------------------
+            "rstep" if input_slice.len() == 2 => {
+                if let Ok(num) = input_slice[1].parse::<usize>() {
+                    CommandResult::Signal(Signal::StepBack(num))
+                } else {
+                    CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + &input_slice[1])
+                }
+            },
+            "rstep" if input_slice.len() == 1 => CommandResult::Signal(Signal::StepBack(1)),
+            "rstep" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "rstep"),
+            "rcontinue" if input_slice.len() == 2 => {
+                if let Some(addr) = Self::parse_address(&input_slice[1]) {
+                    CommandResult::Signal(Signal::ReverseContinue(addr))
+                } else {
+                    CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + &input_slice[1])
+                }
+            },
+            "rcontinue" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "rcontinue"),
+            //----------------------------------------------------------------------------------------------
+            "trace" if input_slice.len() == 2 => {
+                match input_slice[1].as_str() {
+                    "on" => CommandResult::Signal(Signal::Trace(true)),
+                    "off" => CommandResult::Signal(Signal::Trace(false)),
+                    s => CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + s),
+                }
+            },
+            "trace" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "trace"),
+            //----------------------------------------------------------------------------------------------
+            "break" | "unbreak" if input_slice.len() == 2 => {
+                match Self::parse_address(&input_slice[1]) {
+                    Some(addr) if input_slice[0] == "break" => CommandResult::Signal(Signal::AddBreakpoint(addr)),
+                    Some(addr) => CommandResult::Signal(Signal::RemoveBreakpoint(addr)),
+                    None => CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + &input_slice[1]),
+                }
+            },
+            "break" | "unbreak" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + &input_slice[0]),
+            "watch" | "unwatch" if input_slice.len() == 3 || input_slice.len() == 4 => {
+                match Self::parse_watchpoint(&input_slice[1..]) {
+                    Ok(watchpoint) if input_slice[0] == "watch" => CommandResult::Signal(Signal::AddWatchpoint(watchpoint)),
+                    Ok(watchpoint) => CommandResult::Signal(Signal::RemoveWatchpoint(watchpoint)),
+                    Err(arg) => CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + arg),
+                }
+            },
+            "watch" | "unwatch" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + &input_slice[0]),
+            "continue" if input_slice.len() == 2 => {
+                if let Ok(cycles) = input_slice[1].parse::<usize>() {
+                    CommandResult::Signal(Signal::Continue(cycles))
+                } else {
+                    CommandResult::Message(String::from(ERR_INVALID_ARG_STR) + &input_slice[1])
+                }
+            },
+            "continue" if input_slice.len() == 1 => CommandResult::Signal(Signal::Continue(usize::MAX)),
+            "continue" => CommandResult::Message(String::from(ERR_ARG_COUNT_STR) + "continue"),
+            //----------------------------------------------------------------------------------------------
             s => CommandResult::Message(String::from(ERR_UNKNOWN_STR) + s),
         }
     }
+
+    /// parses an address in hex with a `$` or `0x` prefix, or in decimal without one
+    fn parse_address(arg: &str) -> Option<u32> {
+        match arg.strip_prefix('$').or_else(|| arg.strip_prefix("0x")) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => arg.parse::<u32>().ok(),
+        }
+    }
+
+    /// parses `<r|w|rw> <start> [end]`, returning the invalid argument on failure
+    fn parse_watchpoint(args: &[String]) -> Result<Watchpoint, &str> {
+        let kind: WatchKind = match args[0].as_str() {
+            "r" => WatchKind::Read,
+            "w" => WatchKind::Write,
+            "rw" => WatchKind::ReadWrite,
+            s => return Err(s),
+        };
+        let start: u32 = Self::parse_address(&args[1]).ok_or(args[1].as_str())?;
+        let end: u32 = match args.get(2) {
+            Some(arg) => Self::parse_address(arg).ok_or(arg.as_str())?,
+            None => start,
+        };
+        Ok(Watchpoint::new(start, end, kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> CommandResult {
+        CommandParser::parse(&input.split_whitespace().map(String::from).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn test_rcontinue_address() {
+        assert!(matches!(parse("rcontinue $C000"), CommandResult::Signal(Signal::ReverseContinue(0xC000))));
+        assert!(matches!(parse("rcontinue 0xC000"), CommandResult::Signal(Signal::ReverseContinue(0xC000))));
+        assert!(matches!(parse("rcontinue 49152"), CommandResult::Signal(Signal::ReverseContinue(0xC000))));
+        assert!(matches!(parse("rcontinue $G000"), CommandResult::Message(_)));
+    }
+
+    #[test]
+    fn test_breakpoint_address() {
+        assert!(matches!(parse("break $C000"), CommandResult::Signal(Signal::AddBreakpoint(0xC000))));
+        assert!(matches!(parse("break 0xC000"), CommandResult::Signal(Signal::AddBreakpoint(0xC000))));
+        assert!(matches!(parse("unbreak $C000"), CommandResult::Signal(Signal::RemoveBreakpoint(0xC000))));
+        assert!(matches!(parse("unbreak 0xC000"), CommandResult::Signal(Signal::RemoveBreakpoint(0xC000))));
+        assert!(matches!(parse("break 0x"), CommandResult::Message(_)));
+    }
+
+    #[test]
+    fn test_watchpoint_address() {
+        let CommandResult::Signal(Signal::AddWatchpoint(watchpoint)) = parse("watch w $C000 0xC0FF") else {
+            panic!("watch was not parsed");
+        };
+        assert_eq!(watchpoint, Watchpoint::new(0xC000, 0xC0FF, WatchKind::Write));
+        let CommandResult::Signal(Signal::RemoveWatchpoint(watchpoint)) = parse("unwatch rw 0xC000") else {
+            panic!("unwatch was not parsed");
+        };
+        assert_eq!(watchpoint, Watchpoint::new(0xC000, 0xC000, WatchKind::ReadWrite));
+    }
 }
\ No newline at end of file
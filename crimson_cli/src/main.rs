@@ -1,6 +1,7 @@
 mod cli_session;
 mod command;
 mod command_parser;
+mod tracer;
 
 use crate::cli_session::CLISession;
 
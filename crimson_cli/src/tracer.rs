@@ -0,0 +1,24 @@
+use crimson6502::{Instruction, InstructionResult, Observer};
+
+/// prints every executed instruction with the registers it produced
+pub struct Tracer {
+    pub enabled: bool,
+}
+
+impl Tracer {
+    /// traces by default in debug builds
+    pub fn new() -> Tracer {
+        Self {
+            enabled: cfg!(debug_assertions),
+        }
+    }
+}
+
+impl Observer for Tracer {
+    fn after_execute(&mut self, instruction: &Instruction, result: &InstructionResult) {
+        if self.enabled {
+            println!("{:?} {:?}: {:?}, cycles: {:?}, instruction length: {:?}",
+                instruction.mnemonic, instruction.addressing_mode, result.state, result.cycles, result.instruction_length);
+        }
+    }
+}